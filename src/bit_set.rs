@@ -0,0 +1,211 @@
+use std::fmt;
+use std::iter::{FromIterator, FusedIterator};
+
+const WORD_BITS: usize = 64;
+
+/// A set of small integers stored as a fixed-width bitset of `WORDS` 64-bit words.
+///
+/// The width is given in words, not in bits: `StackBitSetWords<2>` holds 128 bits. Sizing the
+/// array from a number of bits (`[u64; (BITS + 63) / 64]`) needs generic const expressions,
+/// which stable Rust doesn't support.
+///
+/// It can hold integers from `0` up to (excluding) [`BITS`](StackBitSetWords::BITS).
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut a = StackBitSetWords::<2>::new();
+/// a.insert(3);
+/// a.insert(100);
+/// let b: StackBitSetWords<2> = vec![3, 5].into_iter().collect();
+///
+/// assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![3]);
+/// assert_eq!(a.union(&b).len(), 3);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StackBitSetWords<const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> StackBitSetWords<WORDS> {
+    /// Number of bits, every stored integer has to be less than that.
+    pub const BITS: usize = WORDS * WORD_BITS;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            words: [0; WORDS],
+        }
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words = [0; WORDS];
+    }
+
+    /// Adds `bit` to the set, returning whether it was not present yet.
+    /// Panics if `bit >= Self::BITS`.
+    pub fn insert(&mut self, bit: usize) -> bool {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(bit: usize, bits: usize) -> ! {
+            panic!("insertion failed: bit (is {}) should be < BITS (is {})", bit, bits);
+        }
+
+        if bit >= Self::BITS {
+            assert_failed(bit, Self::BITS);
+        }
+        let (word, mask) = Self::locate(bit);
+        let inserted = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        inserted
+    }
+
+    /// Removes `bit` from the set, returning whether it was present.
+    pub fn remove(&mut self, bit: usize) -> bool {
+        if !self.contains(bit) {
+            return false;
+        }
+        let (word, mask) = Self::locate(bit);
+        self.words[word] &= !mask;
+        true
+    }
+
+    pub fn contains(&self, bit: usize) -> bool {
+        if bit >= Self::BITS {
+            return false;
+        }
+        let (word, mask) = Self::locate(bit);
+        self.words[word] & mask != 0
+    }
+
+    /// Returns integers present in either `self` or `other`.
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// Returns integers present in both `self` and `other`.
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    /// Returns integers present in `self` but not in `other`.
+    #[inline]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    #[inline]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Iterates over the integers in ascending order.
+    #[inline]
+    pub fn iter(&self) -> BitSetIter<'_> {
+        BitSetIter {
+            words: &self.words,
+            word_idx: 0,
+            word: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    #[inline]
+    fn locate(bit: usize) -> (usize, u64) {
+        (bit / WORD_BITS, 1 << (bit % WORD_BITS))
+    }
+
+    fn zip_with<F: Fn(u64, u64) -> u64>(&self, other: &Self, f: F) -> Self {
+        let mut res = *self;
+        for (word, other) in res.words.iter_mut().zip(other.words.iter()) {
+            *word = f(*word, *other);
+        }
+        res
+    }
+}
+
+impl<const WORDS: usize> Default for StackBitSetWords<WORDS> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> fmt::Debug for StackBitSetWords<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<const WORDS: usize> Extend<usize> for StackBitSetWords<WORDS> {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for bit in iter {
+            self.insert(bit);
+        }
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for StackBitSetWords<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = StackBitSetWords::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, const WORDS: usize> IntoIterator for &'a StackBitSetWords<WORDS> {
+    type Item = usize;
+    type IntoIter = BitSetIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the integers of a [`StackBitSetWords`] in ascending order.
+pub struct BitSetIter<'a> {
+    words: &'a [u64],
+    word_idx: usize,
+    /// Not yet visited bits of `words[word_idx]`.
+    word: u64,
+}
+
+impl Iterator for BitSetIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.word == 0 {
+            self.word_idx += 1;
+            self.word = *self.words.get(self.word_idx)?;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        // clear the lowest set bit
+        self.word &= self.word - 1;
+        Some(self.word_idx * WORD_BITS + bit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest: usize = self.words
+            .iter()
+            .skip(self.word_idx + 1)
+            .map(|word| word.count_ones() as usize)
+            .sum();
+        let len = self.word.count_ones() as usize + rest;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for BitSetIter<'_> {}
+
+impl FusedIterator for BitSetIter<'_> {}
@@ -0,0 +1,269 @@
+use super::{InsertError, NotEnoughSpaceError, SliceVec, StackVec, StackVecView};
+
+/// Common interface of vectors with a fixed capacity, for algorithms accepting any of them.
+///
+/// With the `alloc` feature it is also implemented for [`Vec`], which never grows through this
+/// trait, so its capacity works as the limit.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// fn push_evens<V: CapacityVec<u32> + ?Sized>(vec: &mut V) {
+///     let mut x = 0;
+///     while vec.try_push(x).is_ok() {
+///         x += 2;
+///     }
+/// }
+///
+/// let mut stack = StackVec::<_, 3>::new();
+/// push_evens(&mut stack);
+/// assert_eq!(stack.as_slice(), &[0, 2, 4]);
+///
+/// let mut stack = StackVec::<_, 4>::new();
+/// stack.push(1);
+/// push_evens(stack.as_view_mut());
+/// assert_eq!(stack.as_slice(), &[1, 0, 2, 4]);
+/// ```
+pub trait CapacityVec<T> {
+    /// Number of elements it can hold.
+    fn capacity(&self) -> usize;
+
+    fn len(&self) -> usize;
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    fn is_full(&self) -> bool {
+        self.len() >= self.capacity()
+    }
+
+    /// Pushes a value after the last element returning a `Result`.
+    fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError>;
+
+    /// Pops the last element.
+    /// If exists returns it in `Some`, otherwise `None`.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Inserts a value at specified index by pushing elements from `idx` by one.
+    fn try_insert(&mut self, idx: usize, value: T) -> Result<(), InsertError>;
+
+    /// Removes an element specified by `idx`.
+    /// Panics if `idx >= self.len()`.
+    #[track_caller]
+    fn remove(&mut self, idx: usize) -> T {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(idx: usize, len: usize) -> ! {
+            panic!("removal index (is {}) should be < len (is {})", idx, len);
+        }
+
+        let len = self.len();
+        match self.try_remove(idx) {
+            Some(value) => value,
+            None => assert_failed(idx, len),
+        }
+    }
+
+    /// Removes an element specified by `idx`.
+    /// If exists returns it in `Some`, otherwise `None`.
+    fn try_remove(&mut self, idx: usize) -> Option<T>;
+
+    /// Truncates to specified length.
+    /// Does nothing if `new_len` is greater than current length.
+    fn truncate(&mut self, new_len: usize);
+
+    #[inline]
+    fn clear(&mut self) {
+        self.truncate(0);
+    }
+}
+
+impl<T, const N: usize> CapacityVec<T> for StackVec<T, N> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    #[inline]
+    fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        self.try_push(value)
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    #[inline]
+    fn try_insert(&mut self, idx: usize, value: T) -> Result<(), InsertError> {
+        self.try_insert(idx, value)
+    }
+
+    #[inline]
+    fn remove(&mut self, idx: usize) -> T {
+        self.remove(idx)
+    }
+
+    #[inline]
+    fn try_remove(&mut self, idx: usize) -> Option<T> {
+        self.try_remove(idx)
+    }
+
+    #[inline]
+    fn truncate(&mut self, new_len: usize) {
+        self.truncate(new_len);
+    }
+}
+
+impl<T> CapacityVec<T> for SliceVec<'_, T> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        self.try_push(value)
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    #[inline]
+    fn try_insert(&mut self, idx: usize, value: T) -> Result<(), InsertError> {
+        self.try_insert(idx, value)
+    }
+
+    #[inline]
+    fn remove(&mut self, idx: usize) -> T {
+        self.remove(idx)
+    }
+
+    #[inline]
+    fn try_remove(&mut self, idx: usize) -> Option<T> {
+        self.try_remove(idx)
+    }
+
+    #[inline]
+    fn truncate(&mut self, new_len: usize) {
+        self.truncate(new_len);
+    }
+}
+
+impl<T> CapacityVec<T> for StackVecView<T> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        self.try_push(value)
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    #[inline]
+    fn try_insert(&mut self, idx: usize, value: T) -> Result<(), InsertError> {
+        self.try_insert(idx, value)
+    }
+
+    #[inline]
+    fn remove(&mut self, idx: usize) -> T {
+        self.remove(idx)
+    }
+
+    #[inline]
+    fn try_remove(&mut self, idx: usize) -> Option<T> {
+        self.try_remove(idx)
+    }
+
+    #[inline]
+    fn truncate(&mut self, new_len: usize) {
+        self.truncate(new_len);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> CapacityVec<T> for Vec<T> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        if self.len() < self.capacity() {
+            self.push(value);
+            Ok(())
+        } else {
+            super::cold();
+            Err(NotEnoughSpaceError)
+        }
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn try_insert(&mut self, idx: usize, value: T) -> Result<(), InsertError> {
+        if idx > self.len() {
+            super::cold();
+            return Err(InsertError::IndexOutOfRange);
+        }
+        if self.len() >= self.capacity() {
+            super::cold();
+            return Err(InsertError::NotEnoughSpace);
+        }
+
+        self.insert(idx, value);
+        Ok(())
+    }
+
+    #[inline]
+    fn remove(&mut self, idx: usize) -> T {
+        self.remove(idx)
+    }
+
+    fn try_remove(&mut self, idx: usize) -> Option<T> {
+        if idx < self.len() {
+            Some(self.remove(idx))
+        } else {
+            super::cold();
+            None
+        }
+    }
+
+    #[inline]
+    fn truncate(&mut self, new_len: usize) {
+        self.truncate(new_len);
+    }
+}
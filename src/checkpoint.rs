@@ -0,0 +1,84 @@
+use super::StackVec;
+
+use std::mem;
+use std::ops;
+
+/// A guard which truncates a [`StackVec`] back to its length from the moment the guard was
+/// created, unless [`commit`](Checkpoint::commit) is called.
+///
+/// It derefs to the guarded [`StackVec`], so checkpoints can be nested.
+/// Elements popped below the saved length are not restored.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut vec = StackVec::<_, 8>::new();
+/// vec.push(0);
+/// {
+///     let mut cp = vec.checkpoint();
+///     cp.push(1);
+///     {
+///         let mut inner = cp.checkpoint();
+///         inner.push(2);
+///         // dropped, so `2` is removed
+///     }
+///     assert_eq!(cp.as_slice(), &[0, 1]);
+///     cp.commit();
+/// }
+/// assert_eq!(vec, stack_vec![0, 1]);
+/// ```
+pub struct Checkpoint<'a, T, const N: usize> {
+    vec: &'a mut StackVec<T, N>,
+    len: usize,
+}
+
+impl<T, const N: usize> StackVec<T, N> {
+    /// Creates a [`Checkpoint`] recording the current length.
+    #[inline]
+    pub fn checkpoint(&mut self) -> Checkpoint<'_, T, N> {
+        Checkpoint {
+            len: self.len(),
+            vec: self,
+        }
+    }
+}
+
+impl<T, const N: usize> Checkpoint<'_, T, N> {
+    /// Length the guarded [`StackVec`] is truncated back to.
+    #[inline]
+    pub fn saved_len(&self) -> usize {
+        self.len
+    }
+
+    /// Keeps the changes made since the checkpoint was created.
+    #[inline]
+    pub fn commit(self) {
+        mem::forget(self);
+    }
+
+    /// Truncates the guarded [`StackVec`] back to the saved length, same as dropping the guard.
+    #[inline]
+    pub fn rollback(self) {}
+}
+
+impl<T, const N: usize> Drop for Checkpoint<'_, T, N> {
+    fn drop(&mut self) {
+        self.vec.truncate(self.len);
+    }
+}
+
+impl<T, const N: usize> ops::Deref for Checkpoint<'_, T, N> {
+    type Target = StackVec<T, N>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.vec
+    }
+}
+
+impl<T, const N: usize> ops::DerefMut for Checkpoint<'_, T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.vec
+    }
+}
@@ -0,0 +1,159 @@
+use super::{NotEnoughSpaceError, StackVec};
+
+use std::ptr;
+
+/// A cursor over a [`StackVec`] which can edit the elements around its position.
+///
+/// Same as [`LinkedList`](std::collections::linked_list::CursorMut)'s cursor, it points either to
+/// an element or to the "ghost" position past the last element, and moving wraps around through
+/// the ghost position.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut vec = StackVec::<_, 8>::from_array([1, 2, 3, 4]).unwrap();
+/// let mut cursor = vec.cursor_mut();
+/// while let Some(&mut elem) = cursor.current() {
+///     if elem % 2 == 0 {
+///         cursor.remove_current();
+///     } else {
+///         cursor.insert_after(elem * 10).unwrap();
+///         cursor.move_next();
+///         cursor.move_next();
+///     }
+/// }
+/// assert_eq!(vec.as_slice(), &[1, 10, 3, 30]);
+/// ```
+pub struct CursorMut<'a, T, const N: usize> {
+    vec: &'a mut StackVec<T, N>,
+    /// `vec.len()` for the ghost position.
+    idx: usize,
+}
+
+impl<T, const N: usize> StackVec<T, N> {
+    /// Creates a [`CursorMut`] pointing to the first element,
+    /// or to the ghost position if it is empty.
+    #[inline]
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, N> {
+        CursorMut { vec: self, idx: 0 }
+    }
+}
+
+impl<T, const N: usize> CursorMut<'_, T, N> {
+    /// Index of the current element, `None` at the ghost position.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        if self.idx < self.vec.len() {
+            Some(self.idx)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the current element, `None` at the ghost position.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.vec.get_mut(self.idx)
+    }
+
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = self.next_idx();
+        self.vec.get_mut(next)
+    }
+
+    #[inline]
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = self.prev_idx();
+        self.vec.get_mut(prev)
+    }
+
+    /// Moves to the next element, from the last one to the ghost position and from the ghost
+    /// position to the first one.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.idx = self.next_idx();
+    }
+
+    /// Moves to the previous element, from the first one to the ghost position and from the ghost
+    /// position to the last one.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.idx = self.prev_idx();
+    }
+
+    /// Inserts a value before the current element, or at the end at the ghost position.
+    /// The cursor keeps pointing to the same element.
+    pub fn insert_before(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        self.reserve_one()?;
+        unsafe { self.vec.insert_unchecked(self.idx, value); }
+        self.idx += 1;
+        Ok(())
+    }
+
+    /// Inserts a value after the current element, or at the start at the ghost position.
+    /// The cursor keeps pointing to the same element.
+    pub fn insert_after(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        self.reserve_one()?;
+        if self.idx < self.vec.len() {
+            unsafe { self.vec.insert_unchecked(self.idx + 1, value); }
+        } else {
+            unsafe { self.vec.insert_unchecked(0, value); }
+            self.idx += 1;
+        }
+        Ok(())
+    }
+
+    /// Removes the current element and moves to the next one.
+    /// Returns `None` at the ghost position.
+    #[inline]
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.idx < self.vec.len() {
+            Some(unsafe { self.vec.remove_unchecked(self.idx) })
+        } else {
+            None
+        }
+    }
+
+    /// Moves the current element and all following ones into a new [`StackVec`].
+    /// The cursor ends up at the ghost position.
+    pub fn split_at_cursor(&mut self) -> StackVec<T, N> {
+        let len = self.vec.len();
+        let mut tail = StackVec::new();
+        unsafe {
+            self.vec.set_len(self.idx);
+            let src = self.vec.as_ptr().add(self.idx);
+            ptr::copy_nonoverlapping(src, tail.as_mut_ptr(), len - self.idx);
+            tail.set_len(len - self.idx);
+        }
+        tail
+    }
+
+    #[inline]
+    fn next_idx(&self) -> usize {
+        if self.idx < self.vec.len() {
+            self.idx + 1
+        } else {
+            0
+        }
+    }
+
+    #[inline]
+    fn prev_idx(&self) -> usize {
+        if self.idx > 0 {
+            self.idx - 1
+        } else {
+            self.vec.len()
+        }
+    }
+
+    #[inline]
+    fn reserve_one(&self) -> Result<(), NotEnoughSpaceError> {
+        if self.vec.len() < N {
+            Ok(())
+        } else {
+            super::cold();
+            Err(NotEnoughSpaceError)
+        }
+    }
+}
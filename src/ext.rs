@@ -0,0 +1,92 @@
+use super::{NotEnoughSpaceError, StackVec};
+
+use std::iter::{Fuse, FusedIterator};
+
+/// Extension methods collecting iterators into [`StackVec`]s.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let batches: Vec<_> = (0..7).stack_chunks::<3>().collect();
+/// assert_eq!(batches, vec![stack_vec![0, 1, 2], stack_vec![3, 4, 5], stack_vec![6; cap = 3]]);
+///
+/// assert_eq!((0..3).collect_stack_vec::<4>(), stack_vec![0, 1, 2; cap = 4]);
+/// assert_eq!((0..5).try_collect_stack_vec::<4>(), Err(NotEnoughSpaceError));
+/// ```
+pub trait IteratorExt: Iterator + Sized {
+    /// Yields the elements in batches of `N`, the last batch may be shorter.
+    /// Panics if `N` is zero.
+    fn stack_chunks<const N: usize>(self) -> StackChunks<Self, N> {
+        #[cold]
+        #[track_caller]
+        fn assert_failed() -> ! {
+            panic!("chunk size must be non-zero");
+        }
+
+        if N == 0 {
+            assert_failed();
+        }
+        StackChunks {
+            iter: self.fuse(),
+        }
+    }
+
+    /// Collects the elements into a [`StackVec`].
+    /// Panics if there are more than `N` elements, see also
+    /// [`try_collect_stack_vec`](IteratorExt::try_collect_stack_vec).
+    #[inline]
+    fn collect_stack_vec<const N: usize>(self) -> StackVec<Self::Item, N> {
+        self.collect()
+    }
+
+    /// Collects the elements into a [`StackVec`].
+    /// Returns an error if there are more than `N` elements. In that case the element which does
+    /// not fit has been taken from the iterator and dropped, the following ones are not taken,
+    /// so they can still be read by passing [`by_ref`](Iterator::by_ref).
+    fn try_collect_stack_vec<const N: usize>(
+        mut self,
+    ) -> Result<StackVec<Self::Item, N>, NotEnoughSpaceError> {
+        let mut vec = StackVec::new();
+        for elem in &mut self {
+            vec.try_push(elem)?;
+        }
+        Ok(vec)
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+/// Iterator over [`StackVec`] batches of elements, returned by
+/// [`stack_chunks`](IteratorExt::stack_chunks).
+#[derive(Debug, Clone)]
+pub struct StackChunks<I, const N: usize> {
+    iter: Fuse<I>,
+}
+
+impl<I: Iterator, const N: usize> Iterator for StackChunks<I, N> {
+    type Item = StackVec<I::Item, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = StackVec::new();
+        while chunk.len() < N {
+            match self.iter.next() {
+                Some(elem) => unsafe { chunk.push_unchecked(elem) },
+                None => break,
+            }
+        }
+
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let div_ceil = |len: usize| len / N + (len % N != 0) as usize;
+        (div_ceil(lower), upper.map(div_ceil))
+    }
+}
+
+impl<I: Iterator, const N: usize> FusedIterator for StackChunks<I, N> {}
@@ -0,0 +1,603 @@
+use super::NotEnoughSpaceError;
+
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+use std::iter::{FromIterator, FusedIterator};
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+use std::slice;
+
+/// Marks a vacant bucket in `StackHashMap::tags`.
+const EMPTY: u32 = 0;
+
+/// A hash map with fixed capacity and residing on the stack.
+///
+/// Entries are stored in an inline array of `N` buckets with open addressing (linear probing
+/// and backward shift deletion). To keep probe sequences short, at most
+/// [`CAPACITY`](StackHashMap::CAPACITY) buckets, i.e. 7/8 of `N` rounded down, can be occupied,
+/// so at least one bucket always stays empty.
+///
+/// The default hasher is deterministic, see [`with_hasher`](StackHashMap::with_hasher) for
+/// plugging in a different one.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut map = StackHashMap::<_, _, 16>::new();
+/// map.insert("a", 1);
+/// assert_eq!(map.try_insert("b", 2), Ok(None));
+/// assert_eq!(map.insert("a", 3), Some(1));
+///
+/// assert_eq!(map.get("a"), Some(&3));
+/// assert_eq!(map.len(), 2);
+/// ```
+pub struct StackHashMap<K, V, const N: usize, S = BuildHasherDefault<DefaultHasher>> {
+    tags: [u32; N],
+    buckets: [MaybeUninit<(K, V)>; N],
+    len: usize,
+    hash_builder: S,
+}
+
+impl<K, V, const N: usize, S> Drop for StackHashMap<K, V, N, S> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K, V, const N: usize> StackHashMap<K, V, N> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_hasher(Default::default())
+    }
+}
+
+impl<K, V, const N: usize, S> StackHashMap<K, V, N, S> {
+    /// Maximal number of entries, which keeps the load factor at most 7/8 and one bucket empty.
+    pub const CAPACITY: usize = N - (N + 7) / 8;
+
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            tags: [EMPTY; N],
+            buckets: unsafe { MaybeUninit::uninit().assume_init() },
+            len: 0,
+            hash_builder,
+        }
+    }
+
+    #[inline]
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        for idx in 0..N {
+            if self.tags[idx] != EMPTY {
+                self.tags[idx] = EMPTY;
+                unsafe { ptr::drop_in_place(self.buckets[idx].as_mut_ptr()); }
+            }
+        }
+        self.len = 0;
+    }
+
+    /// Iterates over the entries in an arbitrary order.
+    pub fn iter(&self) -> HashMapIter<'_, K, V> {
+        HashMapIter {
+            tags: self.tags.iter(),
+            buckets: self.buckets.iter(),
+            len: self.len,
+        }
+    }
+
+    /// Iterates over the entries in an arbitrary order.
+    pub fn iter_mut(&mut self) -> HashMapIterMut<'_, K, V> {
+        HashMapIterMut {
+            tags: self.tags.iter(),
+            buckets: self.buckets.iter_mut(),
+            len: self.len,
+        }
+    }
+
+    /// Iterates over the keys in an arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Iterates over the values in an arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Iterates over the values in an arbitrary order.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> + '_ {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    #[inline]
+    fn next_idx(idx: usize) -> usize {
+        if idx + 1 == N {
+            0
+        } else {
+            idx + 1
+        }
+    }
+
+    #[inline]
+    fn ideal_idx(tag: u32) -> usize {
+        tag as usize % N
+    }
+
+    /// Removes the entry at `idx` and closes the gap by shifting back the following entries
+    /// of the probe sequence.
+    unsafe fn remove_at(&mut self, idx: usize) -> (K, V) {
+        let entry = unsafe { ptr::read(self.buckets[idx].as_ptr()) };
+        self.tags[idx] = EMPTY;
+        self.len -= 1;
+
+        let mut hole = idx;
+        let mut idx = idx;
+        loop {
+            idx = Self::next_idx(idx);
+            let tag = self.tags[idx];
+            if tag == EMPTY {
+                break;
+            }
+            // an entry can fill the hole only if its ideal bucket is not in `(hole, idx]`
+            let ideal = Self::ideal_idx(tag);
+            let reachable = if hole < idx {
+                hole < ideal && ideal <= idx
+            } else {
+                hole < ideal || ideal <= idx
+            };
+            if !reachable {
+                unsafe {
+                    let src = self.buckets[idx].as_ptr();
+                    ptr::copy_nonoverlapping(src, self.buckets[hole].as_mut_ptr(), 1);
+                }
+                self.tags[hole] = tag;
+                self.tags[idx] = EMPTY;
+                hole = idx;
+            }
+        }
+        entry
+    }
+
+    unsafe fn write_at(&mut self, idx: usize, tag: u32, key: K, value: V) -> &mut V {
+        self.tags[idx] = tag;
+        self.len += 1;
+        unsafe {
+            let bucket = self.buckets[idx].as_mut_ptr();
+            ptr::write(bucket, (key, value));
+            &mut (*bucket).1
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, const N: usize, S: BuildHasher> StackHashMap<K, V, N, S> {
+    /// Inserts a key-value pair, returning the value previously stored under `key`.
+    /// Panics if `key` is not present and the map is already filled up to
+    /// [`CAPACITY`](StackHashMap::CAPACITY).
+    /// See also [`try_insert`](StackHashMap::try_insert).
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(cap: usize) -> ! {
+            panic!("insertion failed: not enough space in StackHashMap (capacity is {})", cap)
+        }
+
+        match self.try_insert(key, value) {
+            Ok(old) => old,
+            Err(NotEnoughSpaceError) => assert_failed(Self::CAPACITY),
+        }
+    }
+
+    /// Inserts a key-value pair, returning the value previously stored under `key`.
+    /// Returns an error if `key` is not present and the map is already filled up to
+    /// [`CAPACITY`](StackHashMap::CAPACITY).
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, NotEnoughSpaceError> {
+        match self.entry(key) {
+            HashMapEntry::Occupied(mut entry) => Ok(Some(entry.insert(value))),
+            HashMapEntry::Vacant(entry) => entry.try_insert(value).map(|_| None),
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.find(key).ok()?;
+        unsafe { Some(&mut (*self.buckets[idx].as_mut_ptr()).1) }
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.find(key).ok()?;
+        let (key, value) = unsafe { &*self.buckets[idx].as_ptr() };
+        Some((key, value))
+    }
+
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_ok()
+    }
+
+    /// Removes a key from the map, returning its value if it was present.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if it was present.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.find(key).ok()?;
+        unsafe { Some(self.remove_at(idx)) }
+    }
+
+    /// Gets the entry of `key` for in-place manipulation.
+    pub fn entry(&mut self, key: K) -> HashMapEntry<'_, K, V, N, S> {
+        let tag = self.tag(&key);
+        match self.probe(&key, tag) {
+            Ok(idx) => HashMapEntry::Occupied(HashMapOccupiedEntry {
+                map: self,
+                idx,
+            }),
+            Err(idx) => HashMapEntry::Vacant(HashMapVacantEntry {
+                map: self,
+                key,
+                tag,
+                idx,
+            }),
+        }
+    }
+
+    fn tag<Q: Hash + ?Sized>(&self, key: &Q) -> u32 {
+        let mut hasher = self.hash_builder.build_hasher();
+        key.hash(&mut hasher);
+        let hash = hasher.finish();
+        ((hash ^ (hash >> 32)) as u32).max(1)
+    }
+
+    fn find<Q>(&self, key: &Q) -> Result<usize, Option<usize>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.probe(key, self.tag(key))
+    }
+
+    /// Returns the bucket holding `key`,
+    /// otherwise the first vacant bucket of its probe sequence if there is any.
+    fn probe<Q>(&self, key: &Q, tag: u32) -> Result<usize, Option<usize>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        if N == 0 {
+            return Err(None);
+        }
+
+        let mut idx = Self::ideal_idx(tag);
+        for _ in 0..N {
+            match self.tags[idx] {
+                EMPTY => return Err(Some(idx)),
+                t if t == tag => {
+                    let stored = unsafe { &(*self.buckets[idx].as_ptr()).0 };
+                    if stored.borrow() == key {
+                        return Ok(idx);
+                    }
+                }
+                _ => {}
+            }
+            idx = Self::next_idx(idx);
+        }
+        Err(None)
+    }
+}
+
+impl<K, V, const N: usize, S: Default> Default for StackHashMap<K, V, N, S> {
+    #[inline]
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, const N: usize, S> fmt::Debug for StackHashMap<K, V, N, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, const N: usize, S> PartialEq for StackHashMap<K, V, N, S>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+        && self.iter().all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K, V, const N: usize, S> Extend<(K, V)> for StackHashMap<K, V, N, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V, const N: usize, S> FromIterator<(K, V)> for StackHashMap<K, V, N, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = StackHashMap::with_hasher(S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V, const N: usize, S> IntoIterator for &'a StackHashMap<K, V, N, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = HashMapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize, S> IntoIterator for &'a mut StackHashMap<K, V, N, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = HashMapIterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Iterator over the entries of a [`StackHashMap`].
+pub struct HashMapIter<'a, K, V> {
+    tags: slice::Iter<'a, u32>,
+    buckets: slice::Iter<'a, MaybeUninit<(K, V)>>,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for HashMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let tag = *self.tags.next()?;
+            let bucket = self.buckets.next()?;
+            if tag != EMPTY {
+                self.len -= 1;
+                let (key, value) = unsafe { &*bucket.as_ptr() };
+                return Some((key, value));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for HashMapIter<'_, K, V> {}
+
+impl<K, V> FusedIterator for HashMapIter<'_, K, V> {}
+
+/// Mutable iterator over the entries of a [`StackHashMap`].
+pub struct HashMapIterMut<'a, K, V> {
+    tags: slice::Iter<'a, u32>,
+    buckets: slice::IterMut<'a, MaybeUninit<(K, V)>>,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for HashMapIterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let tag = *self.tags.next()?;
+            let bucket = self.buckets.next()?;
+            if tag != EMPTY {
+                self.len -= 1;
+                let (key, value) = unsafe { &mut *bucket.as_mut_ptr() };
+                return Some((&*key, value));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for HashMapIterMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for HashMapIterMut<'_, K, V> {}
+
+/// A view into a single entry of a [`StackHashMap`], returned by [`entry`](StackHashMap::entry).
+pub enum HashMapEntry<'a, K, V, const N: usize, S> {
+    Occupied(HashMapOccupiedEntry<'a, K, V, N, S>),
+    Vacant(HashMapVacantEntry<'a, K, V, N, S>),
+}
+
+/// An entry of a key present in a [`StackHashMap`].
+pub struct HashMapOccupiedEntry<'a, K, V, const N: usize, S> {
+    map: &'a mut StackHashMap<K, V, N, S>,
+    idx: usize,
+}
+
+/// An entry of a key absent from a [`StackHashMap`].
+pub struct HashMapVacantEntry<'a, K, V, const N: usize, S> {
+    map: &'a mut StackHashMap<K, V, N, S>,
+    key: K,
+    tag: u32,
+    /// First vacant bucket of the key's probe sequence.
+    idx: Option<usize>,
+}
+
+impl<'a, K, V, const N: usize, S> HashMapEntry<'a, K, V, N, S> {
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            HashMapEntry::Occupied(entry) => entry.key(),
+            HashMapEntry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant and returns a reference to the value.
+    /// Panics if there is not space available.
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the entry is vacant and returns a reference to the value.
+    /// Panics if there is not space available.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            HashMapEntry::Occupied(entry) => entry.into_mut(),
+            HashMapEntry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Applies `f` to the value if the entry is occupied.
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let HashMapEntry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V: Default, const N: usize, S> HashMapEntry<'a, K, V, N, S> {
+    /// Inserts the default value if the entry is vacant and returns a reference to the value.
+    /// Panics if there is not space available.
+    #[inline]
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K, V, const N: usize, S> HashMapOccupiedEntry<'a, K, V, N, S> {
+    #[inline]
+    pub fn key(&self) -> &K {
+        unsafe { &(*self.map.buckets[self.idx].as_ptr()).0 }
+    }
+
+    #[inline]
+    pub fn get(&self) -> &V {
+        unsafe { &(*self.map.buckets[self.idx].as_ptr()).1 }
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut (*self.map.buckets[self.idx].as_mut_ptr()).1 }
+    }
+
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        unsafe { &mut (*self.map.buckets[self.idx].as_mut_ptr()).1 }
+    }
+
+    /// Replaces the value, returning the old one.
+    #[inline]
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    #[inline]
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    #[inline]
+    pub fn remove_entry(self) -> (K, V) {
+        unsafe { self.map.remove_at(self.idx) }
+    }
+}
+
+impl<'a, K, V, const N: usize, S> HashMapVacantEntry<'a, K, V, N, S> {
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    #[inline]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts a value under the entry's key, panics if there is not space available.
+    /// See also [`try_insert`](HashMapVacantEntry::try_insert).
+    pub fn insert(self, value: V) -> &'a mut V {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(cap: usize) -> ! {
+            panic!("insertion failed: not enough space in StackHashMap (capacity is {})", cap)
+        }
+
+        match self.try_insert(value) {
+            Ok(value) => value,
+            Err(NotEnoughSpaceError) => assert_failed(StackHashMap::<K, V, N, S>::CAPACITY),
+        }
+    }
+
+    /// Inserts a value under the entry's key returning a `Result`.
+    /// Fails if the map is already filled up to [`CAPACITY`](StackHashMap::CAPACITY).
+    pub fn try_insert(self, value: V) -> Result<&'a mut V, NotEnoughSpaceError> {
+        let HashMapVacantEntry { map, key, tag, idx } = self;
+        match idx {
+            Some(idx) if map.len < StackHashMap::<K, V, N, S>::CAPACITY => unsafe {
+                Ok(map.write_at(idx, tag, key, value))
+            },
+            _ => {
+                super::cold();
+                Err(NotEnoughSpaceError)
+            }
+        }
+    }
+}
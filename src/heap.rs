@@ -0,0 +1,262 @@
+use super::{NotEnoughSpaceError, StackVec};
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops;
+use std::slice;
+
+/// Decides which element of a [`StackBinaryHeap`] is closer to its top.
+pub trait HeapKind {
+    /// Returns `true` if `a` should be popped before `b`.
+    fn precedes<T: Ord>(a: &T, b: &T) -> bool;
+}
+
+/// [`HeapKind`] of a max-heap, the greatest element is on top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Max;
+
+/// [`HeapKind`] of a min-heap, the least element is on top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Min;
+
+impl HeapKind for Max {
+    #[inline]
+    fn precedes<T: Ord>(a: &T, b: &T) -> bool {
+        a > b
+    }
+}
+
+impl HeapKind for Min {
+    #[inline]
+    fn precedes<T: Ord>(a: &T, b: &T) -> bool {
+        a < b
+    }
+}
+
+/// A priority queue with fixed capacity, backed by a [`StackVec`].
+///
+/// It is a max-heap by default, pass [`Min`] as the last parameter to get a min-heap.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut heap = StackBinaryHeap::<_, 8, Min>::new();
+/// heap.push(3);
+/// heap.push(1);
+/// heap.push(2);
+///
+/// assert_eq!(heap.peek(), Some(&1));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), Some(2));
+/// ```
+pub struct StackBinaryHeap<T, const N: usize, K = Max> {
+    data: StackVec<T, N>,
+    _kind: PhantomData<K>,
+}
+
+impl<T: Ord, const N: usize, K: HeapKind> StackBinaryHeap<T, N, K> {
+    /// Length of an underlying array.
+    pub const CAPACITY: usize = N;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            data: StackVec::new(),
+            _kind: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the underlying elements in an arbitrary order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Iterates over the elements in an arbitrary order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Returns the top element.
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns a mutable reference to the top element.
+    /// The heap is restored when the returned [`PeekMut`] is dropped.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, N, K>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self })
+        }
+    }
+
+    /// Pushes a value onto the heap, panics if there is not space available.
+    /// See also [`try_push`](StackBinaryHeap::try_push).
+    pub fn push(&mut self, value: T) {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(cap: usize) -> ! {
+            panic!("push failed: not enough space in StackBinaryHeap (capacity is {})", cap);
+        }
+
+        if self.try_push(value).is_err() {
+            assert_failed(Self::CAPACITY);
+        }
+    }
+
+    /// Pushes a value onto the heap returning a `Result`.
+    pub fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        self.data.try_push(value)?;
+        self.sift_up(self.len() - 1);
+        Ok(())
+    }
+
+    /// Pops the top element.
+    /// If exists returns it in `Some`, otherwise `None`.
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len > 1 {
+            self.data.swap(0, len - 1);
+        }
+        let top = self.data.pop()?;
+        self.sift_down(0, self.len());
+        Some(top)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the underlying [`StackVec`] with the elements in an arbitrary order.
+    #[inline]
+    pub fn into_stack_vec(self) -> StackVec<T, N> {
+        self.data
+    }
+
+    /// Returns the elements sorted so that the top of the heap comes last,
+    /// i.e. in ascending order for a [`Max`] heap and in descending order for a [`Min`] heap.
+    pub fn into_sorted_stack_vec(mut self) -> StackVec<T, N> {
+        let mut end = self.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.data
+    }
+
+    fn sift_up(&mut self, mut idx: usize) {
+        while idx > 0 {
+            let parent = (idx - 1) / 2;
+            if !K::precedes(&self.data[idx], &self.data[parent]) {
+                break;
+            }
+            self.data.swap(idx, parent);
+            idx = parent;
+        }
+    }
+
+    /// Sifts the element at `idx` down, considering only the first `end` elements.
+    fn sift_down(&mut self, mut idx: usize, end: usize) {
+        loop {
+            let left = 2 * idx + 1;
+            if left >= end {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < end && K::precedes(&self.data[right], &self.data[left]) {
+                right
+            } else {
+                left
+            };
+            if !K::precedes(&self.data[child], &self.data[idx]) {
+                break;
+            }
+            self.data.swap(idx, child);
+            idx = child;
+        }
+    }
+}
+
+impl<T: Ord, const N: usize, K: HeapKind> Default for StackBinaryHeap<T, N, K> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize, K> fmt::Debug for StackBinaryHeap<T, N, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+/// Builds a heap out of the elements of a [`StackVec`] in linear time.
+impl<T: Ord, const N: usize, K: HeapKind> From<StackVec<T, N>> for StackBinaryHeap<T, N, K> {
+    fn from(vec: StackVec<T, N>) -> Self {
+        let mut heap = Self {
+            data: vec,
+            _kind: PhantomData,
+        };
+        let len = heap.len();
+        for idx in (0..len / 2).rev() {
+            heap.sift_down(idx, len);
+        }
+        heap
+    }
+}
+
+/// Mutable reference to the top element of a [`StackBinaryHeap`],
+/// returned by [`peek_mut`](StackBinaryHeap::peek_mut).
+///
+/// The element is sifted down to its place when this guard is dropped.
+pub struct PeekMut<'a, T: Ord, const N: usize, K: HeapKind = Max> {
+    heap: &'a mut StackBinaryHeap<T, N, K>,
+}
+
+impl<T: Ord, const N: usize, K: HeapKind> PeekMut<'_, T, N, K> {
+    /// Removes the peeked element from the heap and returns it.
+    pub fn pop(this: Self) -> T {
+        // the heap is non-empty for as long as a `PeekMut` exists,
+        // sifting down in `drop` afterwards is a no-op
+        this.heap.pop().unwrap()
+    }
+}
+
+impl<T: Ord, const N: usize, K: HeapKind> Drop for PeekMut<'_, T, N, K> {
+    fn drop(&mut self) {
+        let len = self.heap.len();
+        self.heap.sift_down(0, len);
+    }
+}
+
+impl<T: Ord, const N: usize, K: HeapKind> ops::Deref for PeekMut<'_, T, N, K> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.heap.data[0]
+    }
+}
+
+impl<T: Ord, const N: usize, K: HeapKind> ops::DerefMut for PeekMut<'_, T, N, K> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.heap.data[0]
+    }
+}
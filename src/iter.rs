@@ -1,26 +1,86 @@
 use super::StackVec;
 
-use std::mem::{self, ManuallyDrop, MaybeUninit};
+use std::fmt;
+use std::iter::FusedIterator;
+use std::mem::ManuallyDrop;
+use std::num::NonZeroUsize;
 use std::ptr;
+use std::slice;
 
+/// An iterator moving the elements out of a [`StackVec`].
+///
+/// It takes over the storage of the vector, so creating it doesn't copy the elements.
 pub struct IntoIter<T, const N: usize> {
-    raw_iter: RawIter<T>,
-    initial_len: usize,
-    data: [MaybeUninit<T>; N],
+    /// The remaining elements are `vec.data[head..vec.len]`, so `vec.len` is the tail.
+    vec: ManuallyDrop<StackVec<T, N>>,
+    head: usize,
 }
 
 impl<T, const N: usize> IntoIter<T, N> {
-    pub fn len(&self) -> usize {
-        self.raw_iter.len()
+    /// Returns the remaining elements.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.vec.len - self.head) }
+    }
+
+    /// Returns the remaining elements.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.vec.len - self.head) }
+    }
+
+    /// Moves the remaining elements back into a [`StackVec`], reusing the storage.
+    pub fn into_stack_vec(self) -> StackVec<T, N> {
+        let mut me = ManuallyDrop::new(self);
+        let len = me.vec.len - me.head;
+        unsafe {
+            let data = me.vec.as_mut_ptr();
+            ptr::copy(data.add(me.head), data, len);
+            me.vec.len = len;
+            ManuallyDrop::take(&mut me.vec)
+        }
+    }
+
+    /// Skips `n` elements from the front, dropping them at once.
+    /// Returns the number of steps which could not be made if there are fewer elements.
+    pub fn advance_by(&mut self, n: usize) -> Result<(), NonZeroUsize> {
+        let step = n.min(self.vec.len - self.head);
+        unsafe {
+            let skipped = ptr::slice_from_raw_parts_mut(self.as_mut_ptr(), step);
+            // advance first, so that a panicking destructor can't cause a double drop
+            self.head += step;
+            ptr::drop_in_place(skipped);
+        }
+        NonZeroUsize::new(n - step).map_or(Ok(()), Err)
+    }
+
+    /// Skips `n` elements from the back, dropping them at once.
+    /// Returns the number of steps which could not be made if there are fewer elements.
+    pub fn advance_back_by(&mut self, n: usize) -> Result<(), NonZeroUsize> {
+        let step = n.min(self.vec.len - self.head);
+        unsafe {
+            self.vec.len -= step;
+            let start = self.as_mut_ptr().add(self.vec.len - self.head);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(start, step));
+        }
+        NonZeroUsize::new(n - step).map_or(Ok(()), Err)
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T {
+        unsafe { self.vec.as_ptr().add(self.head) }
+    }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T {
+        unsafe { self.vec.as_mut_ptr().add(self.head) }
     }
 }
 
 impl<T, const N: usize> Drop for IntoIter<T, N> {
     fn drop(&mut self) {
         unsafe {
-            for elem in &mut self.data[0..self.initial_len] {
-                ptr::drop_in_place(elem.as_mut_ptr() as *mut T);
-            }
+            ptr::drop_in_place(self.as_mut_slice());
         }
     }
 }
@@ -30,132 +90,87 @@ impl<T, const N: usize> IntoIterator for StackVec<T, N> {
     type IntoIter = IntoIter<T, N>;
 
     fn into_iter(self) -> Self::IntoIter {
-        unsafe {
-            let me = ManuallyDrop::new(self);
-            let mut into_iter = IntoIter {
-                raw_iter: RawIter {
-                    begin: me.data.as_ptr() as *const T,
-                    end: me.data.as_ptr().add(me.len) as *const T,
-                },
-                initial_len: me.len,
-                data: MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init(),
-            };
-            ptr::copy_nonoverlapping(me.data.as_ptr(), into_iter.data.as_mut_ptr(), me.len);
-            into_iter
+        IntoIter {
+            vec: ManuallyDrop::new(self),
+            head: 0,
         }
     }
 }
 
 impl<T, const N: usize> Iterator for IntoIter<T, N> {
     type Item = T;
-    
+
     fn next(&mut self) -> Option<Self::Item> {
-        self.raw_iter.next()
+        if self.head == self.vec.len {
+            None
+        } else {
+            let next = unsafe { ptr::read(self.as_ptr()) };
+            self.head += 1;
+            Some(next)
+        }
     }
 
+    #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.raw_iter.size_hint()
+        let len = self.vec.len - self.head;
+        (len, Some(len))
     }
-}
 
-impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        self.raw_iter.next_back()
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
     }
-}
 
-// pub struct Drain<'a, T: 'a, const N: usize> {
-//     raw_iter: RawIter<T>,
-//     _phantom: PhantomData<&'a mut StackVec<T, N>>,
-// }
-//
-// impl<T, const N: usize> Iterator for Drain<'_, T, N> {
-//     type Item = T;
-//     
-//     fn next(&mut self) -> Option<Self::Item> {
-//         self.raw_iter.next()
-//     }
-//
-//     fn size_hint(&self) -> (usize, Option<usize>) {
-//         self.raw_iter.size_hint()
-//     }
-// }
-//
-// impl<T, const N: usize> DoubleEndedIterator for Drain<'_, T, N> {
-//     fn next_back(&mut self) -> Option<Self::Item> {
-//         self.raw_iter.next_back()
-//     }
-// }
-//
-// impl<T, const N: usize> StackVec<T, N> {
-//     pub fn drain(&mut self) -> Drain<T, N> {
-//         let raw_iter = RawIter::new(&self);
-//         self.len = 0;
-//         Drain {
-//             raw_iter,
-//             _phantom: PhantomData,
-//         }
-//     }
-// }
-
-struct RawIter<T> {
-    begin: *const T,
-    end: *const T,
-}
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.advance_by(n).ok()?;
+        self.next()
+    }
 
-impl<T> RawIter<T> {
-    // fn new(slice: &[T]) -> Self {
-    //     let end = if mem::size_of::<T>() == 0 {
-    //         (slice.as_ptr() as usize + slice.len()) as *const T
-    //     } else {
-    //         unsafe { slice.as_ptr().add(slice.len()) }
-    //     };
-    //
-    //     Self {
-    //         begin: slice.as_ptr(),
-    //         end,
-    //     }
-    // }
-
-    fn len(&self) -> usize {
-        if mem::size_of::<T>() == 0 {
-            self.end as usize - self.begin as usize
-        } else {
-            (self.end as usize - self.begin as usize)  / mem::size_of::<T>()
-        }
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
     }
 }
 
-impl<T> Iterator for RawIter<T> {
-    type Item = T;
-    
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.begin == self.end {
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.head == self.vec.len {
             None
         } else {
-            unsafe {
-                let next = ptr::read(self.begin);
-                self.begin = self.begin.add(1);
-                Some(next)
-            }
+            self.vec.len -= 1;
+            Some(unsafe { ptr::read(self.vec.as_ptr().add(self.vec.len)) })
         }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.len();
-        (len, Some(len))
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.advance_back_by(n).ok()?;
+        self.next_back()
     }
 }
 
-impl<T> DoubleEndedIterator for RawIter<T> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.begin == self.end {
-            None
-        } else {
-            unsafe {
-                self.end = self.end.sub(1);
-                Some(ptr::read(self.end))
-            }
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T: Clone, const N: usize> Clone for IntoIter<T, N> {
+    fn clone(&self) -> Self {
+        let mut vec = StackVec::new();
+        for elem in self.as_slice() {
+            unsafe { vec.push_unchecked(elem.clone()); }
         }
+        vec.into_iter()
+    }
+}
+
+impl<T, const N: usize> Default for IntoIter<T, N> {
+    #[inline]
+    fn default() -> Self {
+        StackVec::new().into_iter()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
     }
 }
@@ -19,13 +19,73 @@ mod iter;
 pub use iter::IntoIter;
 
 mod macros;
+mod raw;
+
+mod capacity_vec;
+pub use capacity_vec::CapacityVec;
+
+mod checkpoint;
+pub use checkpoint::Checkpoint;
+
+mod cursor;
+pub use cursor::CursorMut;
+
+mod bit_set;
+pub use bit_set::{BitSetIter, StackBitSetWords};
+
+mod ext;
+pub use ext::{IteratorExt, StackChunks};
+
+mod hash_map;
+pub use hash_map::{
+    HashMapEntry, HashMapIter, HashMapIterMut, HashMapOccupiedEntry, HashMapVacantEntry,
+    StackHashMap,
+};
+
+mod heap;
+pub use heap::{HeapKind, Max, Min, PeekMut, StackBinaryHeap};
+
+mod map;
+pub use map::{Entry, OccupiedEntry, StackMap, VacantEntry};
+
+mod non_empty;
+pub use non_empty::NonEmptyStackVec;
+
+mod policy;
+pub use policy::{Checked, DropNew, DropOldest, OverflowPolicy, Panic, PolicyStackVec};
+
+mod ring;
+pub use ring::{RingIter, StackRingBuffer};
+
+mod set;
+pub use set::StackSet;
+
+mod slab;
+pub use slab::{SlabKey, StackSlab};
+
+mod slice_vec;
+pub use slice_vec::{SliceVec, SliceVecDrain};
+
+mod sorted;
+pub use sorted::SortedStackVec;
+
+mod top_k;
+pub use top_k::TopK;
+
+mod view;
+pub use view::StackVecView;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
 
 #[cfg(test)]
 mod tests;
 
-use std::iter::FromIterator;
+use std::alloc::{self, Layout};
+use std::iter::{Chain, FromIterator};
 use std::mem::{self, MaybeUninit};
 use std::ops;
+use std::option;
 use std::ptr;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -37,6 +97,12 @@ pub enum InsertError {
     NotEnoughSpace,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmptyError;
+
+/// The elements which did not fit, returned by [`StackVec::try_from_iter`].
+pub type Overflow<T, I> = Chain<option::IntoIter<T>, I>;
+
 /// A vector-like data structure with fixed capacity and residing on the stack.
 ///
 /// # Example
@@ -51,9 +117,10 @@ pub enum InsertError {
 /// assert_eq!(vec.as_slice(), &[1, 2]);
 /// ```
 #[derive(Debug)]
+#[repr(C)] // the layout is shared with `StackVecView`
 pub struct StackVec<T, const N: usize> {
-    data: [MaybeUninit<T>; N],
     len: usize,
+    data: [MaybeUninit<T>; N],
 }
 
 impl<T, const N: usize> Drop for StackVec<T, N> {
@@ -71,23 +138,53 @@ impl<T, const N: usize> StackVec<T, N> {
     /// Length of an underlying array.
     pub const CAPACITY: usize = N;
 
-    // #[rustversion::since(1.59)] // `MaybeUninit::assume_init` became const
     #[inline]
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
             data: unsafe { MaybeUninit::uninit().assume_init() },
             len: 0,
         }
     }
 
-    // #[rustversion::before(1.59)]
-    // #[inline]
-    // pub fn new() -> Self {
-    //     Self {
-    //         data: unsafe { mem::MaybeUninit::uninit().assume_init() },
-    //         len: 0,
-    //     }
-    // }
+    /// Allocates an empty `StackVec<T, N>` on the heap without building it on the stack first,
+    /// unlike `Box::new(StackVec::new())`, so that large capacities don't overflow the stack.
+    pub fn new_boxed() -> Box<Self> {
+        let layout = Layout::new::<Self>();
+        unsafe {
+            // `len` makes the layout non-zero-sized
+            let ptr = alloc::alloc(layout) as *mut Self;
+            if ptr.is_null() {
+                alloc::handle_alloc_error(layout);
+            }
+            Self::init_in_place(&mut *(ptr as *mut MaybeUninit<Self>));
+            Box::from_raw(ptr)
+        }
+    }
+
+    /// Initializes an empty `StackVec<T, N>` in provided memory, e.g. a `static` or an arena,
+    /// only writing its length.
+    ///
+    /// The elements are dropped only if the `StackVec` itself is, so `slot` has to be dropped in
+    /// place or cleared to drop them.
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut slot = MaybeUninit::<StackVec<u8, 4096>>::uninit();
+    /// let vec = StackVec::init_in_place(&mut slot);
+    /// vec.push(1);
+    /// assert_eq!(vec.as_slice(), &[1]);
+    /// ```
+    #[inline]
+    pub fn init_in_place(slot: &mut MaybeUninit<Self>) -> &mut Self {
+        unsafe {
+            let ptr = slot.as_mut_ptr();
+            ptr::addr_of_mut!((*ptr).len).write(0);
+            &mut *ptr
+        }
+    }
 
     /// Constructs a new `StackVec<T, N>`.
     /// Returns `None` if provided array is longer than `N`.
@@ -95,11 +192,97 @@ impl<T, const N: usize> StackVec<T, N> {
         if M > Self::CAPACITY {
             None
         } else {
-            unsafe {
-                let mut vec = Self::new();
-                ptr::copy_nonoverlapping(arr.as_ptr(), vec.as_mut_ptr(), M);
-                vec.set_len(M);
-                Some(vec)
+            Some(unsafe { Self::from_array_unchecked(arr) })
+        }
+    }
+
+    /// Constructs a new `StackVec<T, N>`.
+    /// Fails to compile if provided array is longer than `N`.
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// let vec = StackVec::<_, 4>::from_array_const([1, 2, 3]);
+    /// assert_eq!(vec.as_slice(), &[1, 2, 3]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use stack_vec::*;
+    /// let vec = stack_vec![1, 2, 3; cap = 2];
+    /// ```
+    #[inline]
+    pub fn from_array_const<const M: usize>(arr: [T; M]) -> Self {
+        let () = AssertFits::<M, N>::OK;
+        unsafe { Self::from_array_unchecked(arr) }
+    }
+
+    /// Constructs a new `StackVec<T, N>` in `const` context, e.g. for a `static`.
+    /// Fails to compile if provided array is longer than `N`.
+    /// See also [`const_stack_vec!`].
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// static PRIMES: StackVec<u32, 8> = StackVec::from_array_in_const([2, 3, 5, 7]);
+    /// assert_eq!(PRIMES.as_slice(), &[2, 3, 5, 7]);
+    /// ```
+    #[rustversion::since(1.83)] // `ptr::copy_nonoverlapping` and `&mut` in const fn
+    pub const fn from_array_in_const<const M: usize>(arr: [T; M]) -> Self {
+        let () = AssertFits::<M, N>::OK;
+        let mut vec = Self::new();
+        unsafe {
+            ptr::copy_nonoverlapping(arr.as_ptr(), vec.data.as_mut_ptr() as *mut T, M);
+        }
+        // the elements were moved into `vec`
+        mem::forget(arr);
+        vec.len = M;
+        vec
+    }
+
+    /// Safety: `M` must not be greater than `N`.
+    #[inline]
+    unsafe fn from_array_unchecked<const M: usize>(arr: [T; M]) -> Self {
+        // the elements are moved into `vec`, so the array itself must not drop them
+        let arr = mem::ManuallyDrop::new(arr);
+        let mut vec = Self::new();
+        unsafe {
+            ptr::copy_nonoverlapping(arr.as_ptr(), vec.as_mut_ptr(), M);
+            vec.set_len(M);
+        }
+        vec
+    }
+
+    /// Collects an iterator without panicking on overflow.
+    /// If the iterator yields more than `N` elements, returns the full vector along with the rest
+    /// of the elements, starting with the first one that did not fit.
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// let (vec, mut rest) = StackVec::<_, 3>::try_from_iter(0..5).unwrap_err();
+    /// assert_eq!(vec, stack_vec![0, 1, 2]);
+    /// assert_eq!(rest.next(), Some(3));
+    ///
+    /// assert!(StackVec::<_, 3>::try_from_iter(0..3).is_ok());
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, (Self, Overflow<T, I::IntoIter>)>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = Self::new();
+        let mut iter = iter.into_iter();
+        while vec.len < Self::CAPACITY {
+            match iter.next() {
+                Some(elem) => unsafe { vec.push_unchecked(elem) },
+                None => return Ok(vec),
+            }
+        }
+
+        match iter.next() {
+            None => Ok(vec),
+            Some(elem) => {
+                cold();
+                Err((vec, Some(elem).into_iter().chain(iter)))
             }
         }
     }
@@ -134,159 +317,215 @@ impl<T, const N: usize> StackVec<T, N> {
     /// Pushes a value after the last element, panics if there is not space available.
     /// See [`try_push`](StackVec::try_push) or [`push_unchecked`](StackVec::push_unchecked) for
     /// related methods.
+    #[inline]
     pub fn push(&mut self, value: T) {
-        #[cold]
-        #[track_caller]
-        fn assert_failed(cap: usize) -> ! {
-            panic!("push failed: not enough space in StackVec (capacity is {})", cap);
-        }
-
-        if self.len < Self::CAPACITY {
-            unsafe { self.push_unchecked(value); }
-        } else {
-            assert_failed(Self::CAPACITY);
-        }
+        self.as_view_mut().push(value);
     }
 
     /// Pushes a value after the last element returning a `Result`.
     /// See also [`push_unchecked`](StackVec::push_unchecked).
+    #[inline]
     pub fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
-        if self.len < Self::CAPACITY {
-            unsafe { self.push_unchecked(value); }
-            Ok(())
-        } else {
-            cold();
-            Err(NotEnoughSpaceError)
-        }
+        self.as_view_mut().try_push(value)
     }
 
     /// Pushes a value after the last element without any checks.
+    #[inline]
     pub unsafe fn push_unchecked(&mut self, value: T) {
-        unsafe {
-            ptr::write(self.as_mut_ptr().add(self.len), value);
-        }
-        self.len += 1;
+        unsafe { self.as_view_mut().push_unchecked(value); }
     }
 
     #[inline]
     pub fn clear(&mut self) {
-        unsafe { self.drop_range(0..self.len); }
-        self.len = 0;
+        self.as_view_mut().clear();
     }
 
     /// Inserts a value at specified index by pushing elements from `idx` by one.
     /// Panics on invalid index.
     /// See also [`try_insert`](StackVec::try_insert) and [`insert_unchecked`](StackVec::insert_unchecked).
+    #[inline]
     pub fn insert(&mut self, idx: usize, value: T) {
-        #[cold]
-        #[track_caller]
-        fn assert_idx_failed(idx: usize, len: usize) -> ! {
-            panic!("insertion index (is {}) should be <= len (is {})", idx, len);
-        }
-
-        #[cold]
-        #[track_caller]
-        fn assert_len_failed(cap: usize) -> ! {
-            panic!("insertion failed: not enough space in StackVec (capacity is {})", cap)
-        }
-
-        if idx > self.len {
-            assert_idx_failed(idx, self.len);
-        }
-        if self.len >= Self::CAPACITY {
-            assert_len_failed(Self::CAPACITY);
-        }
-
-        unsafe { self.insert_unchecked(idx, value); }
+        self.as_view_mut().insert(idx, value);
     }
 
     /// Inserts a value at specified index by pushing elements from `idx` by one.
     /// See also [`insert_unchecked`](StackVec::insert_unchecked).
+    #[inline]
     pub fn try_insert(&mut self, idx: usize, value: T) -> Result<(), InsertError> {
-        if idx > self.len {
-            cold();
-            return Err(InsertError::IndexOutOfRange);
-        }
-        if self.len >= Self::CAPACITY {
-            cold();
-            return Err(InsertError::NotEnoughSpace);
-        }
-
-        unsafe { self.insert_unchecked(idx, value); }
-        Ok(())
+        self.as_view_mut().try_insert(idx, value)
     }
 
     /// Inserts a value at specified index by pushing elements from `idx` by one without performing
     /// any checks.
+    #[inline]
     pub unsafe fn insert_unchecked(&mut self, idx: usize, value: T) {
-        unsafe {
-            let insert_ptr = self.as_mut_ptr().add(idx);
-            ptr::copy(insert_ptr, insert_ptr.add(1), self.len - idx);
-            ptr::write(insert_ptr, value);
-        }
-        self.len += 1;
+        unsafe { self.as_view_mut().insert_unchecked(idx, value); }
     }
 
     /// Pops the last element from a [`StackVec`].
     /// If exists returns it in `Some`, otherwise `None`.
+    #[inline]
     pub fn pop(&mut self) -> Option<T> {
-        if self.len == 0 {
-            None
-        } else {
-            unsafe {
-                self.len -= 1;
-                Some(ptr::read(self.as_ptr().add(self.len)))
-            }
-        }
+        self.as_view_mut().pop()
     }
 
     /// Removes an element specified by `idx`.
     /// Panics if `idx >= self.len`.
     /// See also [`try_remove`](StackVec::try_remove) and [`remove_unchecked`](StackVec::remove_unchecked).
+    #[inline]
     pub fn remove(&mut self, idx: usize) -> T {
-        #[cold]
-        #[track_caller]
-        fn assert_failed(idx: usize, len: usize) -> ! {
-            panic!("removal index (is {}) should be < len (is {})", idx, len);
-        }
-
-        if idx >= self.len {
-            assert_failed(idx, self.len);
-        }
-
-        unsafe { self.remove_unchecked(idx) }
+        self.as_view_mut().remove(idx)
     }
 
     /// Removes an element specified by `idx`.
     /// Returns `None` if `idx` is out of range.
     /// See also [`remove_unchecked`](StackVec::remove_unchecked).
+    #[inline]
     pub fn try_remove(&mut self, idx: usize) -> Option<T> {
-        if idx >= self.len {
-            cold();
-            None
-        } else {
-            unsafe { Some(self.remove_unchecked(idx)) }
-        }
+        self.as_view_mut().try_remove(idx)
     }
 
     /// Removes an element specified by `idx` without any checks.
+    #[inline]
     pub unsafe fn remove_unchecked(&mut self, idx: usize) -> T {
-        unsafe {
-            self.len -= 1;
-            let remove_ptr = self.as_mut_ptr().add(idx);
-            let val = ptr::read(remove_ptr);
-            ptr::copy(remove_ptr.add(1), remove_ptr, self.len - idx);
-            val
-        }
+        unsafe { self.as_view_mut().remove_unchecked(idx) }
     }
 
     /// Truncates a [`StackVec`] to specified length.
     /// Does nothing if `new_len` is greater than current length.
     #[inline]
     pub fn truncate(&mut self, new_len: usize) {
-        let old_len = self.len;
-        unsafe { self.drop_range(new_len..old_len); }
-        self.len = old_len.min(new_len);
+        self.as_view_mut().truncate(new_len);
+    }
+
+    /// Applies `f` to every element, keeping the order and the capacity.
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// let vec = stack_vec![1, 2, 3; cap = 4];
+    /// assert_eq!(vec.map(|x| x * 2), stack_vec![2, 4, 6; cap = 4]);
+    /// ```
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> StackVec<U, N> {
+        let mut src = Consumed::new(self);
+        let mut res = StackVec::new();
+        while let Some(elem) = src.next() {
+            unsafe { res.push_unchecked(f(elem)); }
+        }
+        res
+    }
+
+    /// Applies `f` to every element, keeping the order and the capacity.
+    /// Stops at the first error, dropping the remaining elements and the mapped ones.
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// let vec = stack_vec!["1", "2"; cap = 3];
+    /// assert_eq!(vec.try_map(|s| s.parse::<i32>()), Ok(stack_vec![1, 2; cap = 3]));
+    ///
+    /// let vec = stack_vec!["1", "x"; cap = 2];
+    /// assert!(vec.try_map(|s| s.parse::<i32>()).is_err());
+    /// ```
+    pub fn try_map<U, E, F>(self, mut f: F) -> Result<StackVec<U, N>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let mut src = Consumed::new(self);
+        let mut res = StackVec::new();
+        while let Some(elem) = src.next() {
+            unsafe { res.push_unchecked(f(elem)?); }
+        }
+        Ok(res)
+    }
+
+    /// Pairs up the elements of `self` and `other`.
+    /// The result is as long as the shorter one, the remaining elements of the longer are dropped.
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// let vec = stack_vec![1, 2, 3].zip(stack_vec!['a', 'b'; cap = 3]);
+    /// assert_eq!(vec.as_slice(), &[(1, 'a'), (2, 'b')]);
+    /// ```
+    pub fn zip<U>(self, other: StackVec<U, N>) -> StackVec<(T, U), N> {
+        let mut left = Consumed::new(self);
+        let mut right = Consumed::new(other);
+        let mut res = StackVec::new();
+        while let (Some(a), Some(b)) = (left.next(), right.next()) {
+            unsafe { res.push_unchecked((a, b)); }
+        }
+        res
+    }
+
+    /// Moves the elements satisfying `pred` into the first [`StackVec`] and the rest into the
+    /// second one, keeping their order.
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// let (even, odd) = stack_vec![1, 2, 3, 4, 5; cap = 5].partition(|x| x % 2 == 0);
+    /// assert_eq!(even.as_slice(), &[2, 4]);
+    /// assert_eq!(odd.as_slice(), &[1, 3, 5]);
+    /// ```
+    pub fn partition<F: FnMut(&T) -> bool>(self, mut pred: F) -> (Self, Self) {
+        let mut src = Consumed::new(self);
+        let mut left = Self::new();
+        let mut right = Self::new();
+        while let Some(elem) = src.next() {
+            let dst = if pred(&elem) { &mut left } else { &mut right };
+            unsafe { dst.push_unchecked(elem); }
+        }
+        (left, right)
+    }
+
+    /// Splits the elements into runs where `pred` holds for every pair of neighbours.
+    /// Panics if there are more than `M` runs, see also [`try_group_by`](StackVec::try_group_by).
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// let vec = stack_vec![1, 1, 2, 3, 3; cap = 5];
+    /// let runs = vec.group_by::<3, _>(|a, b| a == b);
+    /// assert_eq!(runs.as_slice(), &[&[1, 1][..], &[2], &[3, 3]]);
+    /// ```
+    pub fn group_by<const M: usize, F>(&self, pred: F) -> StackVec<&[T], M>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(cap: usize) -> ! {
+            panic!("group_by failed: more runs than capacity (is {})", cap);
+        }
+
+        match self.try_group_by(pred) {
+            Ok(runs) => runs,
+            Err(NotEnoughSpaceError) => assert_failed(M),
+        }
+    }
+
+    /// Splits the elements into runs where `pred` holds for every pair of neighbours.
+    /// Returns an error if there are more than `M` runs.
+    pub fn try_group_by<const M: usize, F>(
+        &self,
+        mut pred: F,
+    ) -> Result<StackVec<&[T], M>, NotEnoughSpaceError>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut runs = StackVec::new();
+        let mut rest = self.as_slice();
+        while !rest.is_empty() {
+            let mut len = 1;
+            while len < rest.len() && pred(&rest[len - 1], &rest[len]) {
+                len += 1;
+            }
+            let (run, tail) = rest.split_at(len);
+            runs.try_push(run)?;
+            rest = tail;
+        }
+        Ok(runs)
     }
 
     unsafe fn drop_range(&mut self, range: std::ops::Range<usize>) {
@@ -356,6 +595,26 @@ impl<T: Copy, const N: usize> StackVec<T, N> {
     }
 }
 
+impl<T: Clone, const N: usize> StackVec<T, N> {
+    /// Creates a [`StackVec`] of a given size by cloning provided value, same as `vec![val; len]`.
+    /// The last element is `val` itself.
+    /// Returns `None` if `len` is greater than [`StackVec::CAPACITY`].
+    pub fn from_elem(val: T, len: usize) -> Option<Self> {
+        if len > Self::CAPACITY {
+            return None;
+        }
+
+        let mut vec = Self::new();
+        if len > 0 {
+            for _ in 1..len {
+                unsafe { vec.push_unchecked(val.clone()); }
+            }
+            unsafe { vec.push_unchecked(val); }
+        }
+        Some(vec)
+    }
+}
+
 impl<T: PartialEq, const N: usize> PartialEq for StackVec<T, N> {
     fn eq(&self, other: &Self) -> bool {
         self.len() == other.len()
@@ -393,24 +652,9 @@ impl<T, const N: usize> ops::DerefMut for StackVec<T, N> {
 }
 
 impl<T, const N: usize> Extend<T> for StackVec<T, N> {
+    #[inline]
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        #[cold]
-        #[track_caller]
-        fn assert_failed() -> ! {
-            panic!("Cannot extend `StackVec` with an iterator longer than the available space");
-        }
-
-        let mut iter = iter.into_iter();
-        while let Some(elem) = iter.next() {
-            let len = self.len();
-            if len == Self::CAPACITY {
-                assert_failed();
-            }
-            unsafe {
-                ptr::write(self.as_mut_ptr().add(len), elem);
-                self.len += 1;
-            }
-        }
+        self.as_view_mut().extend(iter);
     }
 }
 
@@ -424,10 +668,7 @@ impl<T, const N: usize> AsMut<[T]> for StackVec<T, N> {
 impl<T, const N: usize> From<[T; N]> for StackVec<T, N> {
     #[inline]
     fn from(arr: [T; N]) -> Self {
-        Self {
-            data: unsafe { mem::transmute_copy(&arr) },
-            len: N,
-        }
+        unsafe { Self::from_array_unchecked(arr) }
     }
 }
 
@@ -447,5 +688,48 @@ impl<T, const N: usize> FromIterator<T> for StackVec<T, N> {
 //     }
 // }
 
+/// Fails to compile when `OK` is used with `M > N`, as constants are evaluated at compile time.
+struct AssertFits<const M: usize, const N: usize>;
+
+impl<const M: usize, const N: usize> AssertFits<M, N> {
+    const OK: () = assert!(M <= N, "array is longer than the capacity of StackVec");
+}
+
+/// Moves the elements out of a [`StackVec`] one by one, dropping the rest when dropped,
+/// also on panic.
+struct Consumed<T, const N: usize> {
+    /// Its length is zeroed, so that it only deallocates.
+    vec: StackVec<T, N>,
+    read: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> Consumed<T, N> {
+    #[inline]
+    fn new(mut vec: StackVec<T, N>) -> Self {
+        let len = mem::replace(&mut vec.len, 0);
+        Self { vec, read: 0, len }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.read < self.len {
+            let elem = unsafe { ptr::read(self.vec.as_ptr().add(self.read)) };
+            self.read += 1;
+            Some(elem)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for Consumed<T, N> {
+    fn drop(&mut self) {
+        unsafe {
+            self.vec.drop_range(self.read..self.len);
+        }
+    }
+}
+
 #[cold]
 fn cold() {}
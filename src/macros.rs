@@ -1,26 +1,154 @@
+/// Creates a [`StackVec`](crate::StackVec), panicking if the elements do not fit.
+///
+/// Besides a list of elements and `elem; length` (cloning `elem`), it takes comprehensions
+/// of the form `expr for pat in iter`. The capacity is given by an optional `; cap = N`.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let squares = stack_vec![i * i for i in 1..4; cap = 5];
+/// assert_eq!(squares.as_slice(), &[1, 4, 9]);
+///
+/// let strings = stack_vec![String::from("a"); 2; cap = 4];
+/// assert_eq!(strings.as_slice(), &["a", "a"]);
+/// ```
 #[macro_export]
 macro_rules! stack_vec {
+    (@comp [$($elem:tt)+] for $pat:pat in $iter:expr; cap = $cap:expr) => {
+        $crate::IteratorExt::collect_stack_vec::<$cap>(
+            ::std::iter::Iterator::map(
+                ::std::iter::IntoIterator::into_iter($iter),
+                |$pat| $($elem)+,
+            ),
+        )
+    };
+    (@comp [$($elem:tt)+] for $pat:pat in $iter:expr) => {
+        $crate::IteratorExt::collect_stack_vec(
+            ::std::iter::Iterator::map(
+                ::std::iter::IntoIterator::into_iter($iter),
+                |$pat| $($elem)+,
+            ),
+        )
+    };
+    (@comp [$($elem:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::stack_vec!(@comp [$($elem)* $next] $($rest)*)
+    };
     () => {
         $crate::StackVec::new()
     };
     ($($elem:expr),+ $(,)?) => {
-        $crate::StackVec::from_array([$($elem),*]).unwrap()
+        $crate::StackVec::from_array_const([$($elem),*])
     };
     ($($elem:expr),*; cap = $cap:expr) => {
-        $crate::StackVec::<_, $cap>::from_array([$($elem),*]).unwrap()
+        $crate::StackVec::<_, $cap>::from_array_const([$($elem),*])
     };
     ($elem:expr; $length:expr) => {
-        $crate::StackVec::from_value($elem, $length).unwrap()
+        $crate::StackVec::from_elem($elem, $length).unwrap()
     };
     ($elem:expr; $length:expr; cap = $cap:expr) => {
-        // $crate::StackVec::from_elem($elem, $length).unwrap()
-        {
-            let mut vec = $crate::StackVec::<_, $cap>::new();
-            for _ in 0..$length {
-                vec.push($elem);
-            }
-            vec
-        }
+        $crate::StackVec::<_, $cap>::from_elem($elem, $length).unwrap()
+    };
+    ($($comp:tt)+) => {
+        $crate::stack_vec!(@comp [] $($comp)+)
+    };
+}
+
+/// Same as [`stack_vec!`], but returns a `Result` with
+/// [`NotEnoughSpaceError`](crate::NotEnoughSpaceError) if the elements do not fit.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let vec = try_stack_vec![1, 2; cap = 3];
+/// assert_eq!(vec, Ok(stack_vec![1, 2; cap = 3]));
+///
+/// let vec = try_stack_vec![i for i in 0..10; cap = 3];
+/// assert_eq!(vec, Err(NotEnoughSpaceError));
+/// ```
+#[macro_export]
+macro_rules! try_stack_vec {
+    (@comp [$($elem:tt)+] for $pat:pat in $iter:expr; cap = $cap:expr) => {
+        $crate::IteratorExt::try_collect_stack_vec::<$cap>(
+            ::std::iter::Iterator::map(
+                ::std::iter::IntoIterator::into_iter($iter),
+                |$pat| $($elem)+,
+            ),
+        )
+    };
+    (@comp [$($elem:tt)+] for $pat:pat in $iter:expr) => {
+        $crate::IteratorExt::try_collect_stack_vec(
+            ::std::iter::Iterator::map(
+                ::std::iter::IntoIterator::into_iter($iter),
+                |$pat| $($elem)+,
+            ),
+        )
+    };
+    (@comp [$($elem:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::try_stack_vec!(@comp [$($elem)* $next] $($rest)*)
+    };
+    () => {
+        ::std::result::Result::<_, $crate::NotEnoughSpaceError>::Ok($crate::StackVec::new())
+    };
+    ($($elem:expr),+ $(,)?) => {
+        $crate::StackVec::from_array([$($elem),*]).ok_or($crate::NotEnoughSpaceError)
+    };
+    ($($elem:expr),*; cap = $cap:expr) => {
+        $crate::StackVec::<_, $cap>::from_array([$($elem),*]).ok_or($crate::NotEnoughSpaceError)
+    };
+    ($elem:expr; $length:expr) => {
+        $crate::StackVec::from_elem($elem, $length).ok_or($crate::NotEnoughSpaceError)
+    };
+    ($elem:expr; $length:expr; cap = $cap:expr) => {
+        $crate::StackVec::<_, $cap>::from_elem($elem, $length).ok_or($crate::NotEnoughSpaceError)
+    };
+    ($($comp:tt)+) => {
+        $crate::try_stack_vec!(@comp [] $($comp)+)
+    };
+}
+
+/// Creates a [`StackVec`](crate::StackVec) in `const` context, e.g. for a `static` lookup table.
+/// Fails to compile if the elements do not fit.
+///
+/// Requires Rust 1.83, see [`StackVec::from_array_in_const`](crate::StackVec::from_array_in_const).
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// const DIGITS: StackVec<char, 16> = const_stack_vec!['0', '1', '2'; cap = 16];
+/// assert_eq!(DIGITS.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! const_stack_vec {
+    ($($elem:expr),+ $(,)?) => {
+        $crate::StackVec::from_array_in_const([$($elem),+])
+    };
+    ($($elem:expr),*; cap = $cap:expr) => {
+        $crate::StackVec::<_, $cap>::from_array_in_const([$($elem),*])
+    };
+}
+
+/// Creates a [`NonEmptyStackVec`](crate::NonEmptyStackVec) from at least one element.
+/// Fails to compile if the elements do not fit.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let vec = nonempty_stack_vec![3, 1, 2; cap = 4];
+/// assert_eq!(vec.first(), &3);
+/// assert_eq!(vec.len(), 3);
+/// ```
+///
+/// ```compile_fail
+/// # use stack_vec::*;
+/// let vec = nonempty_stack_vec![1, 2, 3; cap = 2];
+/// ```
+#[macro_export]
+macro_rules! nonempty_stack_vec {
+    ($($elem:expr),+ $(,)?) => {
+        $crate::NonEmptyStackVec::from_array_const([$($elem),+])
+    };
+    ($($elem:expr),+; cap = $cap:expr) => {
+        $crate::NonEmptyStackVec::<_, $cap>::from_array_const([$($elem),+])
     };
 }
 
@@ -34,3 +162,47 @@ fn initialization() {
     assert_eq!(stack_vec![3, 2, 1; cap = 5], StackVec::<_, 5>::from_array([3, 2, 1]).unwrap());
     assert_eq!(stack_vec![69; 7], StackVec::from([69; 7]));
 }
+
+#[cfg(test)]
+#[test]
+fn nonempty_initialization() {
+    use crate::{NonEmptyStackVec, StackVec};
+    use std::convert::TryFrom;
+    assert_eq!(
+        nonempty_stack_vec![4, 3, 2],
+        NonEmptyStackVec::try_from(StackVec::from([4, 3, 2])).unwrap(),
+    );
+    assert_eq!(nonempty_stack_vec![1; cap = 3].as_stack_vec(), &stack_vec![1; cap = 3]);
+}
+
+#[cfg(test)]
+#[test]
+fn clone_and_comprehension() {
+    use crate::{NotEnoughSpaceError, StackVec};
+    let mut calls = 0;
+    let vec = stack_vec![{ calls += 1; vec![calls] }; 3; cap = 4];
+    assert_eq!(calls, 1);
+    assert_eq!(vec.as_slice(), &[vec![1], vec![1], vec![1]]);
+
+    let vec: StackVec<_, 4> = stack_vec![(x, y) for (x, y) in vec![(1, 'a'), (2, 'b')]];
+    assert_eq!(vec.as_slice(), &[(1, 'a'), (2, 'b')]);
+    assert_eq!(stack_vec![x * 2 for x in 0..3; cap = 3], StackVec::from([0, 2, 4]));
+
+    assert_eq!(try_stack_vec![1, 2, 3; cap = 2], Err(NotEnoughSpaceError));
+    assert_eq!(try_stack_vec![0; 3], Ok(StackVec::from([0; 3])));
+    assert_eq!(try_stack_vec![0; 4; cap = 3], Err(NotEnoughSpaceError));
+    assert_eq!(try_stack_vec![x for x in 0..2], Ok(StackVec::<_, 2>::from([0, 1])));
+    let odd = try_stack_vec![x for x in (0..6).filter(|x| x % 2 == 1); cap = 3];
+    assert_eq!(odd, Ok(StackVec::from([1, 3, 5])));
+}
+
+#[cfg(test)]
+#[rustversion::since(1.83)]
+#[test]
+fn const_initialization() {
+    use crate::StackVec;
+    static NAMES: StackVec<&str, 4> = const_stack_vec!["a", "b"; cap = 4];
+    const EMPTY: StackVec<u8, 2> = const_stack_vec![; cap = 2];
+    assert_eq!(NAMES.as_slice(), &["a", "b"]);
+    assert!(EMPTY.is_empty());
+}
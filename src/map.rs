@@ -0,0 +1,406 @@
+use super::{IntoIter, NotEnoughSpaceError, StackVec};
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::iter::{FromIterator, Zip};
+use std::mem;
+use std::slice;
+
+/// A map with fixed capacity and residing on the stack.
+///
+/// Keys and values are stored in two [`StackVec`]s, with keys kept sorted, so lookups use binary
+/// search and iteration is ordered by keys.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut map = StackMap::<_, _, 4>::new();
+/// assert_eq!(map.insert("b", 2), Ok(None));
+/// assert_eq!(map.insert("a", 1), Ok(None));
+/// assert_eq!(map.insert("b", 3), Ok(Some(2)));
+///
+/// assert_eq!(map.get("b"), Some(&3));
+/// *map.entry("c").or_insert(0) += 5;
+/// assert_eq!(map.keys().as_slice(), &["a", "b", "c"]);
+/// ```
+pub struct StackMap<K, V, const N: usize> {
+    keys: StackVec<K, N>,
+    values: StackVec<V, N>,
+}
+
+impl<K, V, const N: usize> StackMap<K, V, N> {
+    /// Length of the underlying arrays.
+    pub const CAPACITY: usize = N;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            keys: StackVec::new(),
+            values: StackVec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.values.clear();
+    }
+
+    /// Iterates over the keys in ascending order.
+    #[inline]
+    pub fn keys(&self) -> slice::Iter<'_, K> {
+        self.keys.iter()
+    }
+
+    /// Iterates over the values in the ascending order of their keys.
+    #[inline]
+    pub fn values(&self) -> slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Iterates over the values in the ascending order of their keys.
+    #[inline]
+    pub fn values_mut(&mut self) -> slice::IterMut<'_, V> {
+        self.values.iter_mut()
+    }
+
+    /// Iterates over the entries in the ascending order of keys.
+    #[inline]
+    pub fn iter(&self) -> Zip<slice::Iter<'_, K>, slice::Iter<'_, V>> {
+        self.keys.iter().zip(self.values.iter())
+    }
+
+    /// Iterates over the entries in the ascending order of keys.
+    #[inline]
+    pub fn iter_mut(&mut self) -> Zip<slice::Iter<'_, K>, slice::IterMut<'_, V>> {
+        self.keys.iter().zip(self.values.iter_mut())
+    }
+}
+
+impl<K: Ord, V, const N: usize> StackMap<K, V, N> {
+    /// Inserts a key-value pair, returning the value previously stored under `key`.
+    /// Returns an error if `key` is not present and there is not space available.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, NotEnoughSpaceError> {
+        match self.find(&key) {
+            Ok(idx) => Ok(Some(mem::replace(&mut self.values[idx], value))),
+            Err(idx) => {
+                self.try_insert_at(idx, key, value)?;
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).ok().map(|idx| &self.values[idx])
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.find(key) {
+            Ok(idx) => Some(&mut self.values[idx]),
+            Err(_) => None,
+        }
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).ok().map(|idx| (&self.keys[idx], &self.values[idx]))
+    }
+
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_ok()
+    }
+
+    /// Removes a key from the map, returning its value if it was present.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if it was present.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.find(key) {
+            Ok(idx) => unsafe {
+                Some((self.keys.remove_unchecked(idx), self.values.remove_unchecked(idx)))
+            },
+            Err(_) => None,
+        }
+    }
+
+    /// Gets the entry of `key` for in-place manipulation.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N> {
+        match self.find(&key) {
+            Ok(idx) => Entry::Occupied(OccupiedEntry {
+                map: self,
+                idx,
+            }),
+            Err(idx) => Entry::Vacant(VacantEntry {
+                map: self,
+                key,
+                idx,
+            }),
+        }
+    }
+
+    fn find<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.keys.binary_search_by(|probe| probe.borrow().cmp(key))
+    }
+
+    fn try_insert_at(&mut self, idx: usize, key: K, value: V) -> Result<(), NotEnoughSpaceError> {
+        if self.len() == Self::CAPACITY {
+            super::cold();
+            return Err(NotEnoughSpaceError);
+        }
+        unsafe {
+            self.keys.insert_unchecked(idx, key);
+            self.values.insert_unchecked(idx, value);
+        }
+        Ok(())
+    }
+}
+
+impl<K, V, const N: usize> Default for StackMap<K, V, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, const N: usize> fmt::Debug for StackMap<K, V, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq, const N: usize> PartialEq for StackMap<K, V, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.keys == other.keys && self.values == other.values
+    }
+}
+
+impl<K: Ord, V, const N: usize> Extend<(K, V)> for StackMap<K, V, N> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        #[cold]
+        #[track_caller]
+        fn assert_failed() -> ! {
+            panic!("Cannot extend `StackMap` with more keys than the available space");
+        }
+
+        for (key, value) in iter {
+            if self.insert(key, value).is_err() {
+                assert_failed();
+            }
+        }
+    }
+}
+
+impl<K: Ord, V, const N: usize> FromIterator<(K, V)> for StackMap<K, V, N> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = StackMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, const N: usize> IntoIterator for StackMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = Zip<IntoIter<K, N>, IntoIter<V, N>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.keys.into_iter().zip(self.values)
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a StackMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Zip<slice::Iter<'a, K>, slice::Iter<'a, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a mut StackMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = Zip<slice::Iter<'a, K>, slice::IterMut<'a, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// A view into a single entry of a [`StackMap`], returned by [`entry`](StackMap::entry).
+pub enum Entry<'a, K, V, const N: usize> {
+    Occupied(OccupiedEntry<'a, K, V, N>),
+    Vacant(VacantEntry<'a, K, V, N>),
+}
+
+/// An entry of a key present in a [`StackMap`].
+pub struct OccupiedEntry<'a, K, V, const N: usize> {
+    map: &'a mut StackMap<K, V, N>,
+    idx: usize,
+}
+
+/// An entry of a key absent from a [`StackMap`].
+pub struct VacantEntry<'a, K, V, const N: usize> {
+    map: &'a mut StackMap<K, V, N>,
+    key: K,
+    idx: usize,
+}
+
+impl<'a, K: Ord, V, const N: usize> Entry<'a, K, V, N> {
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant and returns a reference to the value.
+    /// Panics if there is not space available.
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the entry is vacant and returns a reference to the value.
+    /// Panics if there is not space available.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Applies `f` to the value if the entry is occupied.
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K: Ord, V: Default, const N: usize> Entry<'a, K, V, N> {
+    /// Inserts the default value if the entry is vacant and returns a reference to the value.
+    /// Panics if there is not space available.
+    #[inline]
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize> OccupiedEntry<'a, K, V, N> {
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.map.keys[self.idx]
+    }
+
+    #[inline]
+    pub fn get(&self) -> &V {
+        &self.map.values[self.idx]
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.values[self.idx]
+    }
+
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.values[self.idx]
+    }
+
+    /// Replaces the value, returning the old one.
+    #[inline]
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    #[inline]
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    pub fn remove_entry(self) -> (K, V) {
+        unsafe {
+            (
+                self.map.keys.remove_unchecked(self.idx),
+                self.map.values.remove_unchecked(self.idx),
+            )
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize> VacantEntry<'a, K, V, N> {
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    #[inline]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts a value under the entry's key, panics if there is not space available.
+    /// See also [`try_insert`](VacantEntry::try_insert).
+    pub fn insert(self, value: V) -> &'a mut V {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(cap: usize) -> ! {
+            panic!("insertion failed: not enough space in StackMap (capacity is {})", cap)
+        }
+
+        match self.try_insert(value) {
+            Ok(value) => value,
+            Err(NotEnoughSpaceError) => assert_failed(N),
+        }
+    }
+
+    /// Inserts a value under the entry's key returning a `Result`.
+    pub fn try_insert(self, value: V) -> Result<&'a mut V, NotEnoughSpaceError> {
+        let VacantEntry { map, key, idx } = self;
+        map.try_insert_at(idx, key, value)?;
+        Ok(&mut map.values[idx])
+    }
+}
@@ -0,0 +1,237 @@
+use super::{EmptyError, InsertError, NotEnoughSpaceError, StackVec};
+
+use std::convert::TryFrom;
+use std::ops;
+use std::slice;
+
+/// A [`StackVec`] which always holds at least one element.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut vec = nonempty_stack_vec![3, 1, 2; cap = 4];
+/// assert_eq!(vec.first(), &3);
+/// assert_eq!(vec.max(), &3);
+///
+/// assert_eq!(vec.pop(), Some(2));
+/// assert_eq!(vec.pop(), Some(1));
+/// assert_eq!(vec.pop(), None);
+/// assert_eq!(vec.last(), &3);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct NonEmptyStackVec<T, const N: usize> {
+    data: StackVec<T, N>,
+}
+
+impl<T, const N: usize> NonEmptyStackVec<T, N> {
+    /// Length of an underlying array.
+    pub const CAPACITY: usize = N;
+
+    /// Constructs a new `NonEmptyStackVec<T, N>` holding `first`.
+    /// Panics if `N` is zero.
+    pub fn new(first: T) -> Self {
+        let mut data = StackVec::new();
+        data.push(first);
+        Self { data }
+    }
+
+    /// Constructs a new `NonEmptyStackVec<T, N>`.
+    /// Returns `None` if provided array is empty or longer than `N`.
+    pub fn from_array<const M: usize>(arr: [T; M]) -> Option<Self> {
+        if M == 0 {
+            None
+        } else {
+            StackVec::from_array(arr).map(|data| Self { data })
+        }
+    }
+
+    /// Constructs a new `NonEmptyStackVec<T, N>`.
+    /// Fails to compile if provided array is empty or longer than `N`.
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// let vec = NonEmptyStackVec::<_, 4>::from_array_const([1, 2, 3]);
+    /// assert_eq!(vec.as_slice(), &[1, 2, 3]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use stack_vec::*;
+    /// let vec = NonEmptyStackVec::<i32, 2>::from_array_const([]);
+    /// ```
+    #[inline]
+    pub fn from_array_const<const M: usize>(arr: [T; M]) -> Self {
+        let () = AssertNonEmpty::<M>::OK;
+        Self {
+            data: StackVec::from_array_const(arr),
+        }
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    #[inline]
+    pub fn as_stack_vec(&self) -> &StackVec<T, N> {
+        &self.data
+    }
+
+    #[inline]
+    pub fn into_stack_vec(self) -> StackVec<T, N> {
+        self.data
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Always returns `false`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn first(&self) -> &T {
+        &self.data[0]
+    }
+
+    #[inline]
+    pub fn first_mut(&mut self) -> &mut T {
+        &mut self.data[0]
+    }
+
+    #[inline]
+    pub fn last(&self) -> &T {
+        &self.data[self.data.len() - 1]
+    }
+
+    #[inline]
+    pub fn last_mut(&mut self) -> &mut T {
+        let last = self.data.len() - 1;
+        &mut self.data[last]
+    }
+
+    /// Pushes a value after the last element, panics if there is not space available.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.data.push(value);
+    }
+
+    /// Pushes a value after the last element returning a `Result`.
+    #[inline]
+    pub fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        self.data.try_push(value)
+    }
+
+    /// Inserts a value at specified index by pushing elements from `idx` by one.
+    /// Panics on invalid index or if there is not space available.
+    #[inline]
+    pub fn insert(&mut self, idx: usize, value: T) {
+        self.data.insert(idx, value);
+    }
+
+    /// Inserts a value at specified index by pushing elements from `idx` by one.
+    #[inline]
+    pub fn try_insert(&mut self, idx: usize, value: T) -> Result<(), InsertError> {
+        self.data.try_insert(idx, value)
+    }
+
+    /// Pops the last element, unless it is the only one left.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.len() == 1 {
+            None
+        } else {
+            self.data.pop()
+        }
+    }
+
+    /// Removes an element specified by `idx`.
+    /// Returns `None` if `idx` is out of range or if it is the only element left.
+    pub fn try_remove(&mut self, idx: usize) -> Option<T> {
+        if self.data.len() == 1 {
+            None
+        } else {
+            self.data.try_remove(idx)
+        }
+    }
+
+    /// Truncates to specified length, but always keeps the first element.
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        self.data.truncate(new_len.max(1));
+    }
+}
+
+impl<T: Ord, const N: usize> NonEmptyStackVec<T, N> {
+    /// Returns the least element, the first one if there are several.
+    pub fn min(&self) -> &T {
+        self.data[1..]
+            .iter()
+            .fold(self.first(), |min, elem| if elem < min { elem } else { min })
+    }
+
+    /// Returns the greatest element, the last one if there are several.
+    pub fn max(&self) -> &T {
+        self.data[1..]
+            .iter()
+            .fold(self.first(), |max, elem| if elem >= max { elem } else { max })
+    }
+}
+
+impl<T, const N: usize> ops::Deref for NonEmptyStackVec<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T, const N: usize> ops::DerefMut for NonEmptyStackVec<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<T, const N: usize> TryFrom<StackVec<T, N>> for NonEmptyStackVec<T, N> {
+    type Error = EmptyError;
+
+    fn try_from(vec: StackVec<T, N>) -> Result<Self, Self::Error> {
+        if vec.is_empty() {
+            Err(EmptyError)
+        } else {
+            Ok(Self { data: vec })
+        }
+    }
+}
+
+impl<T, const N: usize> From<NonEmptyStackVec<T, N>> for StackVec<T, N> {
+    #[inline]
+    fn from(vec: NonEmptyStackVec<T, N>) -> Self {
+        vec.data
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a NonEmptyStackVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+struct AssertNonEmpty<const M: usize>;
+
+impl<const M: usize> AssertNonEmpty<M> {
+    const OK: () = assert!(M > 0, "array of NonEmptyStackVec is empty");
+}
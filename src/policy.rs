@@ -0,0 +1,340 @@
+use super::{NotEnoughSpaceError, StackVec};
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops;
+
+/// Decides what happens when an element is added to a full [`PolicyStackVec`].
+pub trait OverflowPolicy {
+    /// Returned by the adding operations.
+    type Output;
+
+    /// Pushes a value after the last element.
+    fn push<T, const N: usize>(vec: &mut StackVec<T, N>, value: T) -> Self::Output;
+
+    /// Inserts a value at specified index by pushing elements from `idx` by one.
+    /// Panics if `idx > vec.len()`, regardless of the policy.
+    fn insert<T, const N: usize>(vec: &mut StackVec<T, N>, idx: usize, value: T) -> Self::Output;
+
+    /// Pushes the elements of `iter`.
+    fn extend<T, I, const N: usize>(vec: &mut StackVec<T, N>, iter: I) -> Self::Output
+    where
+        I: Iterator<Item = T>;
+}
+
+/// [`OverflowPolicy`] panicking on overflow, same as the methods of [`StackVec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Panic;
+
+/// [`OverflowPolicy`] silently dropping the new elements on overflow (truncating).
+/// Extending stops pulling elements out of the iterator once the vector is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DropNew;
+
+/// [`OverflowPolicy`] dropping the first, i.e. the oldest, element on overflow, so only the last
+/// `N` elements are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DropOldest;
+
+/// [`OverflowPolicy`] returning [`NotEnoughSpaceError`] on overflow.
+/// Extending stops at the first element that does not fit, which is dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Checked;
+
+impl OverflowPolicy for Panic {
+    type Output = ();
+
+    #[inline]
+    fn push<T, const N: usize>(vec: &mut StackVec<T, N>, value: T) {
+        vec.push(value);
+    }
+
+    #[inline]
+    fn insert<T, const N: usize>(vec: &mut StackVec<T, N>, idx: usize, value: T) {
+        vec.insert(idx, value);
+    }
+
+    #[inline]
+    fn extend<T, I, const N: usize>(vec: &mut StackVec<T, N>, iter: I)
+    where
+        I: Iterator<Item = T>,
+    {
+        vec.extend(iter);
+    }
+}
+
+impl OverflowPolicy for DropNew {
+    type Output = ();
+
+    #[inline]
+    fn push<T, const N: usize>(vec: &mut StackVec<T, N>, value: T) {
+        let _ = vec.try_push(value);
+    }
+
+    fn insert<T, const N: usize>(vec: &mut StackVec<T, N>, idx: usize, value: T) {
+        assert_idx(idx, vec.len());
+        let _ = vec.try_insert(idx, value);
+    }
+
+    fn extend<T, I, const N: usize>(vec: &mut StackVec<T, N>, mut iter: I)
+    where
+        I: Iterator<Item = T>,
+    {
+        while vec.len() < N {
+            match iter.next() {
+                Some(elem) => unsafe { vec.push_unchecked(elem) },
+                None => break,
+            }
+        }
+    }
+}
+
+impl OverflowPolicy for DropOldest {
+    type Output = ();
+
+    fn push<T, const N: usize>(vec: &mut StackVec<T, N>, value: T) {
+        if N == 0 {
+            return;
+        }
+        if vec.len() == N {
+            vec.remove(0);
+        }
+        unsafe { vec.push_unchecked(value); }
+    }
+
+    /// Inserting at `0` into a full vector drops `value` itself, as it would be the oldest.
+    fn insert<T, const N: usize>(vec: &mut StackVec<T, N>, idx: usize, value: T) {
+        assert_idx(idx, vec.len());
+        if vec.len() < N {
+            unsafe { vec.insert_unchecked(idx, value); }
+        } else if idx > 0 {
+            vec.remove(0);
+            unsafe { vec.insert_unchecked(idx - 1, value); }
+        }
+    }
+
+    fn extend<T, I, const N: usize>(vec: &mut StackVec<T, N>, iter: I)
+    where
+        I: Iterator<Item = T>,
+    {
+        for elem in iter {
+            Self::push(vec, elem);
+        }
+    }
+}
+
+impl OverflowPolicy for Checked {
+    type Output = Result<(), NotEnoughSpaceError>;
+
+    #[inline]
+    fn push<T, const N: usize>(vec: &mut StackVec<T, N>, value: T) -> Self::Output {
+        vec.try_push(value)
+    }
+
+    fn insert<T, const N: usize>(vec: &mut StackVec<T, N>, idx: usize, value: T) -> Self::Output {
+        assert_idx(idx, vec.len());
+        vec.try_insert(idx, value).map_err(|_| NotEnoughSpaceError)
+    }
+
+    fn extend<T, I, const N: usize>(vec: &mut StackVec<T, N>, iter: I) -> Self::Output
+    where
+        I: Iterator<Item = T>,
+    {
+        for elem in iter {
+            vec.try_push(elem)?;
+        }
+        Ok(())
+    }
+}
+
+#[inline]
+#[track_caller]
+fn assert_idx(idx: usize, len: usize) {
+    #[cold]
+    #[track_caller]
+    fn assert_failed(idx: usize, len: usize) -> ! {
+        panic!("insertion index (is {}) should be <= len (is {})", idx, len);
+    }
+
+    if idx > len {
+        assert_failed(idx, len);
+    }
+}
+
+/// A [`StackVec`] whose adding operations follow an [`OverflowPolicy`] chosen per type.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut window = PolicyStackVec::<_, 3, DropOldest>::new();
+/// window.extend(0..5);
+/// assert_eq!(window.as_slice(), &[2, 3, 4]);
+///
+/// let mut checked = PolicyStackVec::<_, 1, Checked>::new();
+/// assert_eq!(checked.push(0), Ok(()));
+/// assert_eq!(checked.push(1), Err(NotEnoughSpaceError));
+///
+/// let truncated: PolicyStackVec<_, 2, DropNew> = (0..10).collect();
+/// assert_eq!(truncated.as_slice(), &[0, 1]);
+/// ```
+pub struct PolicyStackVec<T, const N: usize, P = Panic> {
+    data: StackVec<T, N>,
+    _policy: PhantomData<P>,
+}
+
+impl<T, const N: usize, P: OverflowPolicy> PolicyStackVec<T, N, P> {
+    /// Length of an underlying array.
+    pub const CAPACITY: usize = N;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            data: StackVec::new(),
+            _policy: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    #[inline]
+    pub fn as_stack_vec(&self) -> &StackVec<T, N> {
+        &self.data
+    }
+
+    #[inline]
+    pub fn into_stack_vec(self) -> StackVec<T, N> {
+        self.data
+    }
+
+    /// Pushes a value after the last element following the policy.
+    #[inline]
+    pub fn push(&mut self, value: T) -> P::Output {
+        P::push(&mut self.data, value)
+    }
+
+    /// Inserts a value at specified index by pushing elements from `idx` by one following the
+    /// policy. Panics on invalid index.
+    #[inline]
+    pub fn insert(&mut self, idx: usize, value: T) -> P::Output {
+        P::insert(&mut self.data, idx, value)
+    }
+
+    /// Pushes the elements of `iter` following the policy.
+    /// See also the [`Extend`] implementation, available for policies which don't report errors.
+    #[inline]
+    pub fn extend_from_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) -> P::Output {
+        P::extend(&mut self.data, iter.into_iter())
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop()
+    }
+
+    /// Removes an element specified by `idx`.
+    /// Panics if `idx >= self.len()`.
+    #[inline]
+    pub fn remove(&mut self, idx: usize) -> T {
+        self.data.remove(idx)
+    }
+
+    #[inline]
+    pub fn try_remove(&mut self, idx: usize) -> Option<T> {
+        self.data.try_remove(idx)
+    }
+
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        self.data.truncate(new_len);
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+impl<T, const N: usize> PolicyStackVec<T, N, Checked> {
+    /// Collects an iterator, returns an error if it yields more than `N` elements.
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, NotEnoughSpaceError> {
+        let mut vec = Self::new();
+        vec.extend_from_iter(iter)?;
+        Ok(vec)
+    }
+}
+
+impl<T, const N: usize, P: OverflowPolicy> Default for PolicyStackVec<T, N, P> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize, P> fmt::Debug for PolicyStackVec<T, N, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize, P> PartialEq for PolicyStackVec<T, N, P> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T, const N: usize, P> ops::Deref for PolicyStackVec<T, N, P> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T, const N: usize, P> ops::DerefMut for PolicyStackVec<T, N, P> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<T, const N: usize, P> From<StackVec<T, N>> for PolicyStackVec<T, N, P> {
+    #[inline]
+    fn from(vec: StackVec<T, N>) -> Self {
+        Self {
+            data: vec,
+            _policy: PhantomData,
+        }
+    }
+}
+
+impl<T, const N: usize, P> Extend<T> for PolicyStackVec<T, N, P>
+where
+    P: OverflowPolicy<Output = ()>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.extend_from_iter(iter);
+    }
+}
+
+impl<T, const N: usize, P> FromIterator<T> for PolicyStackVec<T, N, P>
+where
+    P: OverflowPolicy<Output = ()>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend_from_iter(iter);
+        vec
+    }
+}
@@ -0,0 +1,175 @@
+use super::{InsertError, NotEnoughSpaceError};
+
+use std::mem::MaybeUninit;
+use std::ptr;
+
+/// Storage and length of a vector with a capacity known at runtime.
+///
+/// It holds the adding and removing logic of [`StackVecView`](super::StackVecView), which
+/// [`StackVec`](super::StackVec) delegates to, and of [`SliceVec`](super::SliceVec).
+/// `name` of the panicking methods is the vector type reported in the panic messages.
+pub(crate) struct RawVec<'a, T> {
+    pub(crate) data: &'a mut [MaybeUninit<T>],
+    pub(crate) len: &'a mut usize,
+}
+
+impl<T> RawVec<'_, T> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.data.as_mut_ptr() as _
+    }
+
+    pub(crate) fn push(&mut self, value: T, name: &str) {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(name: &str, cap: usize) -> ! {
+            panic!("push failed: not enough space in {} (capacity is {})", name, cap);
+        }
+
+        if *self.len < self.capacity() {
+            unsafe { self.push_unchecked(value); }
+        } else {
+            assert_failed(name, self.capacity());
+        }
+    }
+
+    pub(crate) fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        if *self.len < self.capacity() {
+            unsafe { self.push_unchecked(value); }
+            Ok(())
+        } else {
+            super::cold();
+            Err(NotEnoughSpaceError)
+        }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn push_unchecked(&mut self, value: T) {
+        unsafe {
+            ptr::write(self.as_mut_ptr().add(*self.len), value);
+        }
+        *self.len += 1;
+    }
+
+    pub(crate) fn insert(&mut self, idx: usize, value: T, name: &str) {
+        #[cold]
+        #[track_caller]
+        fn assert_idx_failed(idx: usize, len: usize) -> ! {
+            panic!("insertion index (is {}) should be <= len (is {})", idx, len);
+        }
+
+        #[cold]
+        #[track_caller]
+        fn assert_len_failed(name: &str, cap: usize) -> ! {
+            panic!("insertion failed: not enough space in {} (capacity is {})", name, cap)
+        }
+
+        if idx > *self.len {
+            assert_idx_failed(idx, *self.len);
+        }
+        if *self.len >= self.capacity() {
+            assert_len_failed(name, self.capacity());
+        }
+
+        unsafe { self.insert_unchecked(idx, value); }
+    }
+
+    pub(crate) fn try_insert(&mut self, idx: usize, value: T) -> Result<(), InsertError> {
+        if idx > *self.len {
+            super::cold();
+            return Err(InsertError::IndexOutOfRange);
+        }
+        if *self.len >= self.capacity() {
+            super::cold();
+            return Err(InsertError::NotEnoughSpace);
+        }
+
+        unsafe { self.insert_unchecked(idx, value); }
+        Ok(())
+    }
+
+    pub(crate) unsafe fn insert_unchecked(&mut self, idx: usize, value: T) {
+        unsafe {
+            let insert_ptr = self.as_mut_ptr().add(idx);
+            ptr::copy(insert_ptr, insert_ptr.add(1), *self.len - idx);
+            ptr::write(insert_ptr, value);
+        }
+        *self.len += 1;
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        if *self.len == 0 {
+            None
+        } else {
+            unsafe {
+                *self.len -= 1;
+                Some(ptr::read(self.as_mut_ptr().add(*self.len)))
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, idx: usize) -> T {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(idx: usize, len: usize) -> ! {
+            panic!("removal index (is {}) should be < len (is {})", idx, len);
+        }
+
+        if idx >= *self.len {
+            assert_failed(idx, *self.len);
+        }
+
+        unsafe { self.remove_unchecked(idx) }
+    }
+
+    pub(crate) fn try_remove(&mut self, idx: usize) -> Option<T> {
+        if idx >= *self.len {
+            super::cold();
+            None
+        } else {
+            unsafe { Some(self.remove_unchecked(idx)) }
+        }
+    }
+
+    pub(crate) unsafe fn remove_unchecked(&mut self, idx: usize) -> T {
+        unsafe {
+            *self.len -= 1;
+            let remove_ptr = self.as_mut_ptr().add(idx);
+            let val = ptr::read(remove_ptr);
+            ptr::copy(remove_ptr.add(1), remove_ptr, *self.len - idx);
+            val
+        }
+    }
+
+    pub(crate) fn truncate(&mut self, new_len: usize) {
+        let old_len = *self.len;
+        if new_len < old_len {
+            // shrink first, so that a panicking destructor can't cause a double drop
+            *self.len = new_len;
+            unsafe {
+                let tail = self.as_mut_ptr().add(new_len);
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(tail, old_len - new_len));
+            }
+        }
+    }
+
+    pub(crate) fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I, name: &str) {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(name: &str) -> ! {
+            panic!("Cannot extend `{}` with an iterator longer than the available space", name);
+        }
+
+        for elem in iter {
+            if *self.len == self.capacity() {
+                assert_failed(name);
+            }
+            unsafe { self.push_unchecked(elem); }
+        }
+    }
+}
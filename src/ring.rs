@@ -0,0 +1,353 @@
+use super::{NotEnoughSpaceError, StackVec};
+
+use std::fmt;
+use std::iter::FusedIterator;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ptr;
+use std::slice;
+
+/// A fixed-capacity ring buffer residing on the stack.
+///
+/// Elements are pushed at the back and popped from the front. When the buffer is full,
+/// [`push_overwrite`](StackRingBuffer::push_overwrite) evicts the oldest element, which makes it
+/// suitable for keeping "the last `N` samples".
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut ring = StackRingBuffer::<_, 3>::new();
+/// for sample in 0..5 {
+///     ring.push_overwrite(sample);
+/// }
+///
+/// assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+/// assert_eq!(ring.into_stack_vec(), stack_vec![2, 3, 4]);
+/// ```
+pub struct StackRingBuffer<T, const N: usize> {
+    data: [MaybeUninit<T>; N],
+    head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> Drop for StackRingBuffer<T, N> {
+    fn drop(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+
+unsafe impl<T: Send, const N: usize> Send for StackRingBuffer<T, N> {}
+unsafe impl<T: Sync, const N: usize> Sync for StackRingBuffer<T, N> {}
+
+impl<T, const N: usize> StackRingBuffer<T, N> {
+    /// Length of an underlying array.
+    pub const CAPACITY: usize = N;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            data: unsafe { MaybeUninit::uninit().assume_init() },
+            head: 0,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == Self::CAPACITY
+    }
+
+    /// Pushes a value after the newest element.
+    /// If the buffer is full, the oldest element is evicted and returned.
+    pub fn push_overwrite(&mut self, value: T) -> Option<T> {
+        if Self::CAPACITY == 0 {
+            return Some(value);
+        }
+
+        if self.is_full() {
+            unsafe {
+                let slot = self.data.as_mut_ptr().add(self.head) as *mut T;
+                let oldest = ptr::replace(slot, value);
+                self.head = self.wrap(self.head + 1);
+                Some(oldest)
+            }
+        } else {
+            unsafe { self.push_back_unchecked(value); }
+            None
+        }
+    }
+
+    /// Pushes a value after the newest element, panics if there is not space available.
+    /// See [`try_push_back`](StackRingBuffer::try_push_back) or
+    /// [`push_overwrite`](StackRingBuffer::push_overwrite) for related methods.
+    pub fn push_back(&mut self, value: T) {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(cap: usize) -> ! {
+            panic!("push failed: not enough space in StackRingBuffer (capacity is {})", cap);
+        }
+
+        if self.is_full() {
+            assert_failed(Self::CAPACITY);
+        }
+        unsafe { self.push_back_unchecked(value); }
+    }
+
+    /// Pushes a value after the newest element returning a `Result`.
+    pub fn try_push_back(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        if self.is_full() {
+            super::cold();
+            Err(NotEnoughSpaceError)
+        } else {
+            unsafe { self.push_back_unchecked(value); }
+            Ok(())
+        }
+    }
+
+    unsafe fn push_back_unchecked(&mut self, value: T) {
+        let idx = self.wrap(self.head + self.len);
+        unsafe {
+            ptr::write(self.data.as_mut_ptr().add(idx) as *mut T, value);
+        }
+        self.len += 1;
+    }
+
+    /// Pops the oldest element.
+    /// If exists returns it in `Some`, otherwise `None`.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            unsafe {
+                let oldest = ptr::read(self.data.as_ptr().add(self.head) as *const T);
+                self.head = self.wrap(self.head + 1);
+                self.len -= 1;
+                Some(oldest)
+            }
+        }
+    }
+
+    /// Pops the newest element.
+    /// If exists returns it in `Some`, otherwise `None`.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            self.len -= 1;
+            let idx = self.wrap(self.head + self.len);
+            unsafe { Some(ptr::read(self.data.as_ptr().add(idx) as *const T)) }
+        }
+    }
+
+    /// Returns the element at `idx`, counting from the oldest one.
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        if idx < self.len {
+            let idx = self.wrap(self.head + idx);
+            unsafe { Some(&*(self.data.as_ptr().add(idx) as *const T)) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the element at `idx`, counting from the oldest one.
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        if idx < self.len {
+            let idx = self.wrap(self.head + idx);
+            unsafe { Some(&mut *(self.data.as_mut_ptr().add(idx) as *mut T)) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the oldest element.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns the newest element.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|idx| self.get(idx))
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        while self.pop_back().is_some() {}
+        self.head = 0;
+    }
+
+    /// Returns the contents as two slices, the first one holding the older elements.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front_len, back_len) = self.slice_lens();
+        unsafe {
+            let ptr = self.data.as_ptr() as *const T;
+            (
+                slice::from_raw_parts(ptr.add(self.head), front_len),
+                slice::from_raw_parts(ptr, back_len),
+            )
+        }
+    }
+
+    /// Returns the contents as two mutable slices, the first one holding the older elements.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front_len, back_len) = self.slice_lens();
+        unsafe {
+            let ptr = self.data.as_mut_ptr() as *mut T;
+            (
+                slice::from_raw_parts_mut(ptr.add(self.head), front_len),
+                slice::from_raw_parts_mut(ptr, back_len),
+            )
+        }
+    }
+
+    /// Rotates the underlying array, so that the elements are stored contiguously from the oldest
+    /// to the newest, and returns them as a slice.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.head + self.len > Self::CAPACITY {
+            self.data.rotate_left(self.head);
+            self.head = 0;
+        }
+        self.as_mut_slices().0
+    }
+
+    /// Converts into a [`StackVec`] ordered from the oldest to the newest element.
+    pub fn into_stack_vec(mut self) -> StackVec<T, N> {
+        self.make_contiguous();
+        let me = ManuallyDrop::new(self);
+        let mut vec = StackVec::new();
+        unsafe {
+            let src = (me.data.as_ptr() as *const T).add(me.head);
+            ptr::copy_nonoverlapping(src, vec.as_mut_ptr(), me.len);
+            vec.set_len(me.len);
+        }
+        vec
+    }
+
+    /// Iterates from the oldest to the newest element.
+    pub fn iter(&self) -> RingIter<'_, T> {
+        let (front, back) = self.as_slices();
+        RingIter {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+
+    fn slice_lens(&self) -> (usize, usize) {
+        let front_len = self.len.min(Self::CAPACITY - self.head);
+        (front_len, self.len - front_len)
+    }
+
+    #[inline]
+    fn wrap(&self, idx: usize) -> usize {
+        if idx >= Self::CAPACITY {
+            idx - Self::CAPACITY
+        } else {
+            idx
+        }
+    }
+}
+
+impl<T, const N: usize> Default for StackRingBuffer<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for StackRingBuffer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for StackRingBuffer<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+        && self.iter()
+            .zip(other.iter())
+            .all(|(a, b)| a == b)
+    }
+}
+
+impl<T, const N: usize> From<StackVec<T, N>> for StackRingBuffer<T, N> {
+    fn from(vec: StackVec<T, N>) -> Self {
+        let vec = ManuallyDrop::new(vec);
+        let mut ring = Self::new();
+        unsafe {
+            ptr::copy_nonoverlapping(vec.as_ptr(), ring.data.as_mut_ptr() as *mut T, vec.len());
+        }
+        ring.len = vec.len();
+        ring
+    }
+}
+
+/// Pushes every element with [`push_overwrite`](StackRingBuffer::push_overwrite), so only the
+/// last `N` elements are kept.
+impl<T, const N: usize> Extend<T> for StackRingBuffer<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_overwrite(elem);
+        }
+    }
+}
+
+impl<T, const N: usize> IntoIterator for StackRingBuffer<T, N> {
+    type Item = T;
+    type IntoIter = super::IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_stack_vec().into_iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a StackRingBuffer<T, N> {
+    type Item = &'a T;
+    type IntoIter = RingIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the elements of a [`StackRingBuffer`], from the oldest to the newest.
+pub struct RingIter<'a, T> {
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for RingIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for RingIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for RingIter<'_, T> {}
+
+impl<T> FusedIterator for RingIter<'_, T> {}
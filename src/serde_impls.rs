@@ -0,0 +1,51 @@
+use super::StackMap;
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+impl<K: Serialize, V: Serialize, const N: usize> Serialize for StackMap<K, V, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, K, V, const N: usize> Deserialize<'de> for StackMap<K, V, N>
+where
+    K: Deserialize<'de> + Ord,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MapVisitor<K, V, const N: usize>(PhantomData<StackMap<K, V, N>>);
+
+        impl<'de, K, V, const N: usize> Visitor<'de> for MapVisitor<K, V, N>
+        where
+            K: Deserialize<'de> + Ord,
+            V: Deserialize<'de>,
+        {
+            type Value = StackMap<K, V, N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a map with at most {} entries", N)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                let mut map = StackMap::new();
+                while let Some((key, value)) = access.next_entry()? {
+                    if map.insert(key, value).is_err() {
+                        return Err(de::Error::invalid_length(N + 1, &self));
+                    }
+                }
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+}
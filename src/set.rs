@@ -0,0 +1,183 @@
+use super::{NotEnoughSpaceError, SortedStackVec};
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::slice;
+
+/// A set with fixed capacity and residing on the stack.
+///
+/// Elements are kept sorted in a [`SortedStackVec`] without duplicates, so lookups use binary
+/// search and iteration is ordered.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut set = StackSet::<_, 4>::new();
+/// assert!(set.insert(3));
+/// assert!(set.insert(1));
+/// assert!(!set.insert(3));
+///
+/// assert!(set.contains(&1));
+/// assert_eq!(set.as_slice(), &[1, 3]);
+/// ```
+pub struct StackSet<T, const N: usize> {
+    data: SortedStackVec<T, N>,
+}
+
+impl<T: Ord, const N: usize> StackSet<T, N> {
+    /// Length of an underlying array.
+    pub const CAPACITY: usize = N;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            data: SortedStackVec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the elements in ascending order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Iterates over the elements in ascending order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool {
+        self.data.contains(value)
+    }
+
+    /// Adds a value to the set, returning whether it was not present yet.
+    /// Panics if the value is not present and there is not space available.
+    /// See also [`try_insert`](StackSet::try_insert).
+    pub fn insert(&mut self, value: T) -> bool {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(cap: usize) -> ! {
+            panic!("insertion failed: not enough space in StackSet (capacity is {})", cap)
+        }
+
+        match self.try_insert(value) {
+            Ok(inserted) => inserted,
+            Err(NotEnoughSpaceError) => assert_failed(Self::CAPACITY),
+        }
+    }
+
+    /// Adds a value to the set, returning whether it was not present yet.
+    /// Returns an error if the value is not present and there is not space available.
+    pub fn try_insert(&mut self, value: T) -> Result<bool, NotEnoughSpaceError> {
+        if self.data.contains(&value) {
+            Ok(false)
+        } else {
+            self.data.try_insert(value).map(|_| true)
+        }
+    }
+
+    /// Removes a value from the set, returning whether it was present.
+    #[inline]
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.data.remove_value(value).is_some()
+    }
+
+    /// Removes a value from the set, returning the stored one if it was present.
+    #[inline]
+    pub fn take(&mut self, value: &T) -> Option<T> {
+        self.data.remove_value(value)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the underlying [`SortedStackVec`].
+    #[inline]
+    pub fn into_sorted_stack_vec(self) -> SortedStackVec<T, N> {
+        self.data
+    }
+}
+
+impl<T: Ord + Clone, const N: usize> StackSet<T, N> {
+    /// Returns elements present in either `self` or `other`.
+    /// Returns an error if the result does not fit in capacity `N`.
+    #[inline]
+    pub fn union<const M: usize>(&self, other: &StackSet<T, M>) -> Result<Self, NotEnoughSpaceError> {
+        self.data.union(&other.data).map(|data| Self { data })
+    }
+
+    /// Returns elements present in both `self` and `other`.
+    #[inline]
+    pub fn intersection<const M: usize>(&self, other: &StackSet<T, M>) -> Self {
+        Self {
+            data: self.data.intersection(&other.data),
+        }
+    }
+
+    /// Returns elements present in `self` but not in `other`.
+    #[inline]
+    pub fn difference<const M: usize>(&self, other: &StackSet<T, M>) -> Self {
+        Self {
+            data: self.data.difference(&other.data),
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> Default for StackSet<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for StackSet<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.data.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for StackSet<T, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T: Ord, const N: usize> Extend<T> for StackSet<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.insert(elem);
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> FromIterator<T> for StackSet<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = StackSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, T: Ord, const N: usize> IntoIterator for &'a StackSet<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,223 @@
+use super::StackVec;
+
+use std::fmt;
+use std::mem;
+
+/// Marks the end of the free list in `StackSlab::free_head`.
+const NO_FREE: usize = usize::MAX;
+
+/// Stable handle of an element stored in a [`StackSlab`].
+///
+/// Keys are generational: once the element is removed, its key no longer matches the slot even
+/// if the slot gets reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SlabKey {
+    index: usize,
+    generation: u32,
+}
+
+impl SlabKey {
+    /// Index of the slot, unique among the currently stored elements.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+enum SlotState<T> {
+    Occupied(T),
+    /// Vacant slots form an intrusive list of free slots.
+    Vacant { next_free: usize },
+}
+
+struct Slot<T> {
+    generation: u32,
+    state: SlotState<T>,
+}
+
+/// A pool of elements with fixed capacity, residing on the stack, where elements keep their
+/// [`SlabKey`]s through removals of other elements.
+///
+/// Slots of removed elements are reused by following insertions.
+///
+/// Keys are always generational. Making the generation optional would need a second key type
+/// or a type parameter on every slab, while it only costs a `u32` per slot and per key.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut slab = StackSlab::<_, 4>::new();
+/// let a = slab.insert("a").unwrap();
+/// let b = slab.insert("b").unwrap();
+///
+/// assert_eq!(slab.remove(a), Some("a"));
+/// assert_eq!(slab.get(b), Some(&"b"));
+/// assert_eq!(slab.get(a), None);
+///
+/// let c = slab.insert("c").unwrap();
+/// assert_eq!(c.index(), a.index());
+/// assert_eq!(slab.get(a), None);
+/// ```
+pub struct StackSlab<T, const N: usize> {
+    slots: StackVec<Slot<T>, N>,
+    free_head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> StackSlab<T, N> {
+    /// Length of an underlying array.
+    pub const CAPACITY: usize = N;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            slots: StackVec::new(),
+            free_head: NO_FREE,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == Self::CAPACITY
+    }
+
+    /// Stores a value and returns its key.
+    /// Returns the value back if there is not space available.
+    pub fn insert(&mut self, value: T) -> Result<SlabKey, T> {
+        if self.free_head != NO_FREE {
+            let index = self.free_head;
+            let slot = &mut self.slots[index];
+            if let SlotState::Vacant { next_free } = slot.state {
+                self.free_head = next_free;
+            }
+            slot.state = SlotState::Occupied(value);
+            self.len += 1;
+            Ok(SlabKey {
+                index,
+                generation: slot.generation,
+            })
+        } else if self.slots.len() < Self::CAPACITY {
+            let index = self.slots.len();
+            unsafe {
+                self.slots.push_unchecked(Slot {
+                    generation: 0,
+                    state: SlotState::Occupied(value),
+                });
+            }
+            self.len += 1;
+            Ok(SlabKey {
+                index,
+                generation: 0,
+            })
+        } else {
+            super::cold();
+            Err(value)
+        }
+    }
+
+    /// Removes the element of `key`.
+    /// If exists returns it in `Some`, otherwise `None`.
+    pub fn remove(&mut self, key: SlabKey) -> Option<T> {
+        self.get(key)?;
+
+        let slot = &mut self.slots[key.index];
+        let vacant = SlotState::Vacant {
+            next_free: self.free_head,
+        };
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free_head = key.index;
+        self.len -= 1;
+        match mem::replace(&mut slot.state, vacant) {
+            SlotState::Occupied(value) => Some(value),
+            SlotState::Vacant { .. } => unreachable!(),
+        }
+    }
+
+    pub fn get(&self, key: SlabKey) -> Option<&T> {
+        match self.slots.get(key.index) {
+            Some(Slot {
+                generation,
+                state: SlotState::Occupied(value),
+            }) if *generation == key.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: SlabKey) -> Option<&mut T> {
+        match self.slots.get_mut(key.index) {
+            Some(Slot {
+                generation,
+                state: SlotState::Occupied(value),
+            }) if *generation == key.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, key: SlabKey) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes all elements, invalidating their keys.
+    pub fn clear(&mut self) {
+        // generations are kept, so that stale keys don't match the reused slots
+        self.free_head = NO_FREE;
+        for index in (0..self.slots.len()).rev() {
+            let slot = &mut self.slots[index];
+            if let SlotState::Occupied(_) = slot.state {
+                slot.generation = slot.generation.wrapping_add(1);
+            }
+            slot.state = SlotState::Vacant {
+                next_free: self.free_head,
+            };
+            self.free_head = index;
+        }
+        self.len = 0;
+    }
+
+    /// Iterates over the stored elements and their keys in the order of slots.
+    pub fn iter(&self) -> impl Iterator<Item = (SlabKey, &T)> + '_ {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| match &slot.state {
+            SlotState::Occupied(value) => {
+                let key = SlabKey { index, generation: slot.generation };
+                Some((key, value))
+            }
+            SlotState::Vacant { .. } => None,
+        })
+    }
+
+    /// Iterates over the stored elements and their keys in the order of slots.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (SlabKey, &mut T)> + '_ {
+        self.slots.iter_mut().enumerate().filter_map(|(index, slot)| match &mut slot.state {
+            SlotState::Occupied(value) => {
+                let key = SlabKey { index, generation: slot.generation };
+                Some((key, value))
+            }
+            SlotState::Vacant { .. } => None,
+        })
+    }
+}
+
+impl<T, const N: usize> Default for StackSlab<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for StackSlab<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,358 @@
+use super::raw::RawVec;
+use super::{InsertError, NotEnoughSpaceError, StackVec};
+
+use std::fmt;
+use std::iter::FusedIterator;
+use std::mem::MaybeUninit;
+use std::ops::{self, Bound, RangeBounds};
+use std::ptr;
+use std::slice;
+
+/// A vector-like data structure over borrowed, possibly uninitialized storage, e.g. a `static`
+/// pool or an arena allocation.
+///
+/// Its capacity is the length of the storage. The elements are dropped along with the
+/// `SliceVec`, unless it borrows a [`StackVec`] (see [`StackVec::as_slice_vec`]).
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// use std::mem::MaybeUninit;
+///
+/// let mut buf = [MaybeUninit::<String>::uninit(), MaybeUninit::uninit()];
+/// let mut vec = SliceVec::new(&mut buf);
+/// vec.push("a".to_string());
+/// assert_eq!(vec.try_push("b".to_string()), Ok(()));
+/// assert_eq!(vec.try_push("c".to_string()), Err(NotEnoughSpaceError));
+/// assert_eq!(vec.as_slice(), &["a", "b"]);
+/// ```
+pub struct SliceVec<'a, T> {
+    data: &'a mut [MaybeUninit<T>],
+    len: usize,
+    /// Length of the borrowed [`StackVec`], written back when dropped.
+    owner_len: Option<&'a mut usize>,
+}
+
+impl<T, const N: usize> StackVec<T, N> {
+    /// Borrows the [`StackVec`] as a [`SliceVec`], for code which handles both.
+    ///
+    /// Until the [`SliceVec`] is dropped, the [`StackVec`] is empty, so forgetting the
+    /// [`SliceVec`] only leaks the elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// let mut vec = StackVec::<_, 4>::from_array([1, 2]).unwrap();
+    /// vec.as_slice_vec().push(3);
+    /// assert_eq!(vec.as_slice(), &[1, 2, 3]);
+    /// ```
+    pub fn as_slice_vec(&mut self) -> SliceVec<'_, T> {
+        let len = self.len;
+        self.len = 0;
+        SliceVec {
+            data: &mut self.data,
+            len,
+            owner_len: Some(&mut self.len),
+        }
+    }
+}
+
+impl<'a, T> SliceVec<'a, T> {
+    /// Constructs a new, empty `SliceVec<T>` over `data`.
+    #[inline]
+    pub fn new(data: &'a mut [MaybeUninit<T>]) -> Self {
+        Self {
+            data,
+            len: 0,
+            owner_len: None,
+        }
+    }
+
+    /// Length of the underlying storage.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.data.as_ptr() as _
+    }
+
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.data.as_mut_ptr() as _
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len) }
+    }
+
+    /// Pushes a value after the last element, panics if there is not space available.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.raw().push(value, "SliceVec");
+    }
+
+    /// Pushes a value after the last element returning a `Result`.
+    #[inline]
+    pub fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        self.raw().try_push(value)
+    }
+
+    /// Inserts a value at specified index by pushing elements from `idx` by one.
+    /// Panics on invalid index or if there is not space available.
+    #[inline]
+    pub fn insert(&mut self, idx: usize, value: T) {
+        self.raw().insert(idx, value, "SliceVec");
+    }
+
+    /// Inserts a value at specified index by pushing elements from `idx` by one.
+    #[inline]
+    pub fn try_insert(&mut self, idx: usize, value: T) -> Result<(), InsertError> {
+        self.raw().try_insert(idx, value)
+    }
+
+    /// Pops the last element.
+    /// If exists returns it in `Some`, otherwise `None`.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.raw().pop()
+    }
+
+    /// Removes an element specified by `idx`.
+    /// Panics if `idx >= self.len()`.
+    #[inline]
+    pub fn remove(&mut self, idx: usize) -> T {
+        self.raw().remove(idx)
+    }
+
+    /// Removes an element specified by `idx`.
+    /// If exists returns it in `Some`, otherwise `None`.
+    #[inline]
+    pub fn try_remove(&mut self, idx: usize) -> Option<T> {
+        self.raw().try_remove(idx)
+    }
+
+    /// Truncates to specified length.
+    /// Does nothing if `new_len` is greater than current length.
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        self.raw().truncate(new_len);
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    #[inline]
+    fn raw(&mut self) -> RawVec<'_, T> {
+        RawVec {
+            data: &mut *self.data,
+            len: &mut self.len,
+        }
+    }
+
+    /// Removes the elements in `range`, returning them in an iterator.
+    /// The elements not taken out of the iterator are dropped along with it.
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// # use stack_vec::*;
+    /// let mut vec = StackVec::<_, 5>::from_array([0, 1, 2, 3, 4]).unwrap();
+    /// let drained: Vec<_> = vec.as_slice_vec().drain(1..3).collect();
+    /// assert_eq!(drained, vec![1, 2]);
+    /// assert_eq!(vec.as_slice(), &[0, 3, 4]);
+    /// ```
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> SliceVecDrain<'_, 'a, T> {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(start: usize, end: usize, len: usize) -> ! {
+            panic!("drain range ({}..{}) out of bounds (len is {})", start, end, len);
+        }
+
+        #[cold]
+        #[track_caller]
+        fn overflow_failed() -> ! {
+            panic!("attempted to drain a range bounded by usize::MAX + 1");
+        }
+
+        let len = self.len;
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).unwrap_or_else(|| overflow_failed()),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).unwrap_or_else(|| overflow_failed()),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        if start > end || end > len {
+            assert_failed(start, end, len);
+        }
+
+        // the drained and the following elements are restored by the iterator when dropped
+        self.len = start;
+        SliceVecDrain {
+            vec: self,
+            head: start,
+            back: end,
+            tail_start: end,
+            tail_len: len - end,
+        }
+    }
+}
+
+impl<T> Drop for SliceVec<'_, T> {
+    fn drop(&mut self) {
+        match self.owner_len.take() {
+            Some(owner_len) => *owner_len = self.len,
+            None => self.clear(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SliceVec<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for SliceVec<'_, T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T> ops::Deref for SliceVec<'_, T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T> ops::DerefMut for SliceVec<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> Extend<T> for SliceVec<'_, T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.raw().extend(iter, "SliceVec");
+    }
+}
+
+/// An iterator removing a range of elements from a [`SliceVec`],
+/// returned by [`drain`](SliceVec::drain).
+pub struct SliceVecDrain<'v, 'a, T> {
+    /// Its length is the start of the drained range.
+    vec: &'v mut SliceVec<'a, T>,
+    /// The remaining drained elements are `head..back`.
+    head: usize,
+    back: usize,
+    /// The elements following the drained range are `tail_start..tail_start + tail_len`.
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<T> SliceVecDrain<'_, '_, T> {
+    /// Returns the remaining drained elements.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.vec.as_ptr().add(self.head), self.back - self.head) }
+    }
+}
+
+impl<T> Iterator for SliceVecDrain<'_, '_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.head == self.back {
+            None
+        } else {
+            let next = unsafe { ptr::read(self.vec.as_ptr().add(self.head)) };
+            self.head += 1;
+            Some(next)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.head;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for SliceVecDrain<'_, '_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.head == self.back {
+            None
+        } else {
+            self.back -= 1;
+            Some(unsafe { ptr::read(self.vec.as_ptr().add(self.back)) })
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for SliceVecDrain<'_, '_, T> {}
+
+impl<T> FusedIterator for SliceVecDrain<'_, '_, T> {}
+
+impl<T> Drop for SliceVecDrain<'_, '_, T> {
+    fn drop(&mut self) {
+        /// Moves the tail back even if dropping a drained element panics.
+        struct MoveTail<'d, 'v, 'a, T>(&'d mut SliceVecDrain<'v, 'a, T>);
+
+        impl<T> Drop for MoveTail<'_, '_, '_, T> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let start = drain.vec.len;
+                unsafe {
+                    let ptr = drain.vec.as_mut_ptr();
+                    ptr::copy(ptr.add(drain.tail_start), ptr.add(start), drain.tail_len);
+                }
+                drain.vec.len = start + drain.tail_len;
+            }
+        }
+
+        let guard = MoveTail(self);
+        let drain = &mut *guard.0;
+        let rest = drain.back - drain.head;
+        let head = drain.head;
+        drain.head = drain.back;
+        unsafe {
+            let rest_ptr = drain.vec.as_mut_ptr().add(head);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(rest_ptr, rest));
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use super::{NotEnoughSpaceError, StackVec};
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{self, Bound, RangeBounds};
+use std::ptr;
+
+/// A [`StackVec`] which keeps its elements sorted in ascending order.
+///
+/// Equal elements are kept in the order of insertion.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let mut vec = SortedStackVec::<_, 8>::new();
+/// assert_eq!(vec.insert(5), 0);
+/// assert_eq!(vec.insert(1), 0);
+/// assert_eq!(vec.insert(3), 1);
+///
+/// assert_eq!(vec.as_slice(), &[1, 3, 5]);
+/// assert!(vec.contains(&3));
+/// assert_eq!(vec.range(2..), &[3, 5]);
+/// ```
+pub struct SortedStackVec<T, const N: usize> {
+    data: StackVec<T, N>,
+}
+
+impl<T: Ord, const N: usize> SortedStackVec<T, N> {
+    /// Length of an underlying array.
+    pub const CAPACITY: usize = N;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            data: StackVec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Inserts a value at its sorted position and returns the position.
+    /// Panics if there is not space available.
+    /// See also [`try_insert`](SortedStackVec::try_insert).
+    pub fn insert(&mut self, value: T) -> usize {
+        #[cold]
+        #[track_caller]
+        fn assert_failed(cap: usize) -> ! {
+            panic!("insertion failed: not enough space in SortedStackVec (capacity is {})", cap)
+        }
+
+        match self.try_insert(value) {
+            Ok(idx) => idx,
+            Err(NotEnoughSpaceError) => assert_failed(Self::CAPACITY),
+        }
+    }
+
+    /// Inserts a value at its sorted position and returns the position.
+    pub fn try_insert(&mut self, value: T) -> Result<usize, NotEnoughSpaceError> {
+        if self.data.len() == Self::CAPACITY {
+            super::cold();
+            return Err(NotEnoughSpaceError);
+        }
+
+        let idx = self.upper_bound(&value);
+        unsafe { self.data.insert_unchecked(idx, value); }
+        Ok(idx)
+    }
+
+    /// Removes the first element equal to `value`.
+    /// If exists returns it in `Some`, otherwise `None`.
+    pub fn remove_value(&mut self, value: &T) -> Option<T> {
+        let idx = self.lower_bound(value);
+        if self.data.get(idx) == Some(value) {
+            unsafe { Some(self.data.remove_unchecked(idx)) }
+        } else {
+            None
+        }
+    }
+
+    /// Removes an element specified by `idx`.
+    /// Panics if `idx >= self.len()`.
+    #[inline]
+    pub fn remove(&mut self, idx: usize) -> T {
+        self.data.remove(idx)
+    }
+
+    /// Pops the greatest element.
+    /// If exists returns it in `Some`, otherwise `None`.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop()
+    }
+
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        self.data.truncate(new_len);
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Checks if an element equal to `value` is present using binary search.
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool {
+        self.data.binary_search(value).is_ok()
+    }
+
+    /// Returns the elements contained in `range`.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> &[T] {
+        let start = match range.start_bound() {
+            Bound::Included(value) => self.lower_bound(value),
+            Bound::Excluded(value) => self.upper_bound(value),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(value) => self.upper_bound(value),
+            Bound::Excluded(value) => self.lower_bound(value),
+            Bound::Unbounded => self.data.len(),
+        };
+        &self.data[start..end.max(start)]
+    }
+
+    /// Moves all elements of `other` into `self`, keeping it sorted.
+    /// Returns `other` back if there is not enough space for its elements.
+    pub fn merge(&mut self, mut other: Self) -> Result<(), Self> {
+        let self_len = self.data.len();
+        let other_len = other.data.len();
+        if self_len + other_len > Self::CAPACITY {
+            super::cold();
+            return Err(other);
+        }
+
+        // merge from the back, so that no element is overwritten before being moved;
+        // lengths are zeroed beforehand, so a panicking `cmp` leaks instead of double dropping
+        unsafe {
+            self.data.set_len(0);
+            other.data.set_len(0);
+            let dst = self.data.as_mut_ptr();
+            let src = other.data.as_ptr();
+            let (mut i, mut j) = (self_len, other_len);
+            while j > 0 {
+                if i > 0 && *dst.add(i - 1) > *src.add(j - 1) {
+                    ptr::copy_nonoverlapping(dst.add(i - 1), dst.add(i + j - 1), 1);
+                    i -= 1;
+                } else {
+                    ptr::copy_nonoverlapping(src.add(j - 1), dst.add(i + j - 1), 1);
+                    j -= 1;
+                }
+            }
+            self.data.set_len(self_len + other_len);
+        }
+        Ok(())
+    }
+
+    /// Returns the underlying [`StackVec`].
+    #[inline]
+    pub fn into_stack_vec(self) -> StackVec<T, N> {
+        self.data
+    }
+
+    /// Index of the first element not less than `value`.
+    fn lower_bound(&self, value: &T) -> usize {
+        self.partition_point(|probe| probe < value)
+    }
+
+    /// Index of the first element greater than `value`.
+    fn upper_bound(&self, value: &T) -> usize {
+        self.partition_point(|probe| probe <= value)
+    }
+
+    fn partition_point<P: FnMut(&T) -> bool>(&self, mut pred: P) -> usize {
+        self.data
+            .binary_search_by(|probe| if pred(probe) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            })
+            .unwrap_err()
+    }
+}
+
+impl<T: Ord + Clone, const N: usize> SortedStackVec<T, N> {
+    /// Returns elements present in either `self` or `other`.
+    /// Equal elements present in both are taken once.
+    /// Returns an error if the result does not fit in capacity `N`.
+    pub fn union<const M: usize>(
+        &self,
+        other: &SortedStackVec<T, M>,
+    ) -> Result<Self, NotEnoughSpaceError> {
+        let mut res = Self::new();
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) => match x.cmp(y) {
+                    Ordering::Less => a.next(),
+                    Ordering::Greater => b.next(),
+                    Ordering::Equal => {
+                        b.next();
+                        a.next()
+                    }
+                },
+                (Some(_), None) => a.next(),
+                (None, Some(_)) => b.next(),
+                (None, None) => break,
+            };
+            if let Some(elem) = next {
+                res.data.try_push(elem.clone())?;
+            }
+        }
+        Ok(res)
+    }
+
+    /// Returns elements present in both `self` and `other`.
+    pub fn intersection<const M: usize>(&self, other: &SortedStackVec<T, M>) -> Self {
+        let mut res = Self::new();
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            match x.cmp(y) {
+                Ordering::Less => {
+                    a.next();
+                }
+                Ordering::Greater => {
+                    b.next();
+                }
+                Ordering::Equal => {
+                    unsafe { res.data.push_unchecked((*x).clone()); }
+                    a.next();
+                    b.next();
+                }
+            }
+        }
+        res
+    }
+
+    /// Returns elements present in `self` but not in `other`.
+    pub fn difference<const M: usize>(&self, other: &SortedStackVec<T, M>) -> Self {
+        let mut res = Self::new();
+        let mut b = other.iter().peekable();
+        for x in self.iter() {
+            while b.next_if(|y| *y < x).is_some() {}
+            if b.next_if(|y| *y == x).is_none() {
+                unsafe { res.data.push_unchecked(x.clone()); }
+            }
+        }
+        res
+    }
+}
+
+impl<T: Ord, const N: usize> Default for SortedStackVec<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SortedStackVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for SortedStackVec<T, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T, const N: usize> ops::Deref for SortedStackVec<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T: Ord, const N: usize> From<StackVec<T, N>> for SortedStackVec<T, N> {
+    fn from(mut vec: StackVec<T, N>) -> Self {
+        vec.sort();
+        Self {
+            data: vec,
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> FromIterator<T> for SortedStackVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        StackVec::from_iter(iter).into()
+    }
+}
@@ -179,4 +179,763 @@ mod drop {
         }
         assert_drop::<40>(10, func);
     }
+
+    #[test]
+    fn into_iter_nth() {
+        fn func(vec: StackVec<DropTracker, 10>) {
+            let mut iter = vec.into_iter();
+            assert!(iter.nth(2).is_some());
+            assert!(iter.nth_back(3).is_some());
+            assert_eq!(iter.len(), 3);
+            assert_eq!(iter.into_stack_vec().len(), 3);
+        }
+        assert_drop(10, func);
+    }
+
+    #[test]
+    fn from_array() {
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        drop(StackVec::<_, 2>::from([Counted(&drops), Counted(&drops)]));
+        drop(StackVec::<_, 3>::from_array([Counted(&drops), Counted(&drops)]));
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn try_map() {
+        fn func(vec: StackVec<DropTracker, 10>) {
+            let mut count = 0;
+            let res = vec.try_map(|tracker| {
+                count += 1;
+                if count == 4 { Err(tracker) } else { Ok(tracker) }
+            });
+            assert_eq!(res.err().map(|_| ()), Some(()));
+        }
+        assert_drop(8, func);
+    }
+
+    #[test]
+    fn zip() {
+        fn func(vec: StackVec<DropTracker, 10>) {
+            let other: StackVec<_, 10> = (0..3).collect();
+            assert_eq!(vec.zip(other).len(), 3);
+        }
+        assert_drop(8, func);
+    }
+}
+
+mod ring {
+    use super::*;
+
+    #[test]
+    fn push_overwrite() {
+        let mut ring = StackRingBuffer::<_, 3>::new();
+        assert_eq!(ring.push_overwrite(0), None);
+        assert_eq!(ring.push_overwrite(1), None);
+        assert_eq!(ring.push_overwrite(2), None);
+        assert_eq!(ring.push_overwrite(3), Some(0));
+        assert_eq!(ring.push_overwrite(4), Some(1));
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(ring.iter().rev().copied().collect::<Vec<_>>(), vec![4, 3, 2]);
+        assert_eq!(ring.as_slices(), (&[2][..], &[3, 4][..]));
+        assert_eq!(ring.try_push_back(5), Err(NotEnoughSpaceError));
+        assert_eq!(ring.pop_front(), Some(2));
+        assert_eq!(ring.back(), Some(&4));
+
+        let mut ring = StackRingBuffer::<i32, 0>::new();
+        assert_eq!(ring.push_overwrite(0), Some(0));
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn linearize() {
+        let mut ring: StackRingBuffer<_, 4> = StackVec::from([0, 1, 2, 3]).into();
+        ring.extend(4..7);
+        assert_eq!(ring.make_contiguous(), &[3, 4, 5, 6]);
+        ring.push_overwrite(7);
+        assert_eq!(ring.into_stack_vec(), stack_vec![4, 5, 6, 7]);
+    }
+}
+
+mod heap {
+    use super::*;
+
+    #[test]
+    fn push_pop() {
+        let mut heap = StackBinaryHeap::<_, 5>::new();
+        for x in [3, 1, 4, 1, 5].iter() {
+            heap.push(*x);
+        }
+        assert_eq!(heap.try_push(9), Err(NotEnoughSpaceError));
+        assert_eq!(heap.peek(), Some(&5));
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, vec![5, 4, 3, 1, 1]);
+
+        let mut heap: StackBinaryHeap<_, 5, Min> = StackVec::from([3, 1, 4, 1, 5]).into();
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, vec![1, 1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn peek_mut() {
+        let mut heap: StackBinaryHeap<_, 4> = StackVec::from([2, 8, 4, 6]).into();
+        *heap.peek_mut().unwrap() = 1;
+        assert_eq!(heap.peek(), Some(&6));
+        assert_eq!(PeekMut::pop(heap.peek_mut().unwrap()), 6);
+        assert_eq!(heap.into_sorted_stack_vec(), stack_vec![1, 2, 4]);
+    }
+}
+
+mod top_k {
+    use super::*;
+
+    #[test]
+    fn push() {
+        let mut top = TopK::<_, 3>::new();
+        assert_eq!(top.push(5), None);
+        assert_eq!(top.push(1), None);
+        assert_eq!(top.push(7), None);
+        assert_eq!(top.push(3), Some(1));
+        assert_eq!(top.push(2), Some(2));
+        assert_eq!(top.as_slice(), &[7, 5, 3]);
+        assert_eq!(top.worst(), Some(&3));
+
+        let top: TopK<_, 0> = (0..10).collect();
+        assert!(top.is_empty());
+    }
+
+    #[test]
+    fn push_by_key() {
+        let mut top = TopK::<_, 2>::new();
+        for word in ["aaa", "b", "cc", "ddd", "e"].iter() {
+            top.push_by_key(*word, |w| w.len());
+        }
+        // ties keep the older element
+        assert_eq!(top.into_sorted(), stack_vec!["aaa", "ddd"]);
+    }
+}
+
+mod sorted {
+    use super::*;
+
+    #[test]
+    fn insert_remove() {
+        let mut vec = SortedStackVec::<_, 5>::new();
+        assert_eq!(vec.insert(4), 0);
+        assert_eq!(vec.insert(2), 0);
+        assert_eq!(vec.insert(4), 2);
+        assert_eq!(vec.insert(3), 1);
+        assert_eq!(vec.try_insert(0), Ok(0));
+        assert_eq!(vec.try_insert(1), Err(NotEnoughSpaceError));
+        assert_eq!(vec.as_slice(), &[0, 2, 3, 4, 4]);
+        assert_eq!(vec.remove_value(&4), Some(4));
+        assert_eq!(vec.remove_value(&1), None);
+        assert_eq!(vec.as_slice(), &[0, 2, 3, 4]);
+        assert!(vec.contains(&2));
+        assert!(!vec.contains(&5));
+    }
+
+    #[test]
+    fn range() {
+        let vec: SortedStackVec<_, 8> = stack_vec![5, 1, 3, 3, 7, 9; cap = 8].into();
+        assert_eq!(vec.range(3..7), &[3, 3, 5]);
+        assert_eq!(vec.range(3..=7), &[3, 3, 5, 7]);
+        assert_eq!(vec.range(..4), &[1, 3, 3]);
+        assert_eq!(vec.range(8..), &[9]);
+        assert!(vec.range((ops::Bound::Included(6), ops::Bound::Excluded(2))).is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let mut a: SortedStackVec<_, 6> = vec![1, 4, 6].into_iter().collect();
+        let b: SortedStackVec<_, 6> = vec![2, 4, 5].into_iter().collect();
+        a.merge(b).unwrap();
+        assert_eq!(a.as_slice(), &[1, 2, 4, 4, 5, 6]);
+        let c: SortedStackVec<_, 6> = vec![3].into_iter().collect();
+        assert!(a.merge(c).is_err());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: SortedStackVec<_, 5> = vec![1, 2, 3, 5, 8].into_iter().collect();
+        let b: SortedStackVec<_, 4> = vec![2, 3, 4, 9].into_iter().collect();
+        assert_eq!(a.intersection(&b).as_slice(), &[2, 3]);
+        assert_eq!(a.difference(&b).as_slice(), &[1, 5, 8]);
+        assert_eq!(a.union(&b), Err(NotEnoughSpaceError));
+        let c: SortedStackVec<_, 2> = vec![2, 5].into_iter().collect();
+        assert_eq!(a.union(&c).unwrap().as_slice(), &[1, 2, 3, 5, 8]);
+    }
+}
+
+mod map {
+    use super::*;
+
+    #[test]
+    fn insert_remove() {
+        let mut map = StackMap::<_, _, 3>::new();
+        assert_eq!(map.insert(3, "c"), Ok(None));
+        assert_eq!(map.insert(1, "a"), Ok(None));
+        assert_eq!(map.insert(2, "b"), Ok(None));
+        assert_eq!(map.insert(2, "B"), Ok(Some("b")));
+        assert_eq!(map.insert(4, "d"), Err(NotEnoughSpaceError));
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &"a"), (&2, &"B"), (&3, &"c")]);
+        assert_eq!(map.remove(&1), Some("a"));
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.get(&3), Some(&"c"));
+        *map.get_mut(&3).unwrap() = "C";
+        assert_eq!(map.values().collect::<Vec<_>>(), vec![&"B", &"C"]);
+    }
+
+    #[test]
+    fn entry() {
+        let mut map = StackMap::<_, _, 2>::new();
+        for word in ["a", "b", "a", "a"].iter() {
+            *map.entry(*word).or_insert(0) += 1;
+        }
+        assert_eq!(map.get("a"), Some(&3));
+        assert_eq!(map.get("b"), Some(&1));
+        match map.entry("c") {
+            Entry::Vacant(entry) => assert_eq!(entry.try_insert(1), Err(NotEnoughSpaceError)),
+            Entry::Occupied(_) => unreachable!(),
+        }
+        match map.entry("b") {
+            Entry::Occupied(entry) => assert_eq!(entry.remove(), 1),
+            Entry::Vacant(_) => unreachable!(),
+        }
+        assert_eq!(map.len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let map: StackMap<_, _, 4> = vec![("b", 2), ("a", 1)].into_iter().collect();
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"a":1,"b":2}"#);
+        let de: StackMap<String, i32, 2> = serde_json::from_str(&json).unwrap();
+        assert_eq!(de.get("b"), Some(&2));
+        assert!(serde_json::from_str::<StackMap<String, i32, 1>>(&json).is_err());
+    }
+}
+
+mod hash_map {
+    use super::*;
+
+    use std::hash::BuildHasherDefault;
+
+    /// Puts every key into the same bucket to exercise probing and backward shift deletion.
+    #[derive(Default)]
+    struct ConstHasher;
+
+    impl std::hash::Hasher for ConstHasher {
+        fn finish(&self) -> u64 {
+            3
+        }
+
+        fn write(&mut self, _: &[u8]) {}
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut map = StackHashMap::<_, _, 8>::new();
+        assert_eq!(StackHashMap::<i32, i32, 8>::CAPACITY, 7);
+        assert_eq!(StackHashMap::<i32, i32, 4>::CAPACITY, 3);
+        assert_eq!(StackHashMap::<i32, i32, 1>::CAPACITY, 0);
+        for i in 0..7 {
+            assert_eq!(map.try_insert(i, i * 10), Ok(None));
+        }
+        assert_eq!(map.try_insert(7, 70), Err(NotEnoughSpaceError));
+        assert_eq!(map.try_insert(3, 33), Ok(Some(30)));
+        assert_eq!(map.remove(&5), Some(50));
+        assert_eq!(map.remove(&5), None);
+        assert_eq!(map.try_insert(7, 70), Ok(None));
+        let mut entries: Vec<_> = map.iter().map(|(k, v)| (*k, *v)).collect();
+        entries.sort_unstable();
+        assert_eq!(entries, vec![(0, 0), (1, 10), (2, 20), (3, 33), (4, 40), (6, 60), (7, 70)]);
+    }
+
+    #[test]
+    fn collisions() {
+        let mut map = StackHashMap::<_, _, 5, BuildHasherDefault<ConstHasher>>::default();
+        for i in 0..4 {
+            map.insert(i, i);
+        }
+        assert_eq!(map.remove(&1), Some(1));
+        assert_eq!(map.get(&3), Some(&3));
+        assert_eq!(map.remove(&0), Some(0));
+        assert_eq!(map.get(&2), Some(&2));
+        assert_eq!(map.get(&3), Some(&3));
+        map.insert(5, 5);
+        assert_eq!(map.len(), 3);
+        assert!(map.contains_key(&5));
+        assert!(!map.contains_key(&0));
+    }
+
+    #[test]
+    fn entry() {
+        let mut map = StackHashMap::<_, _, 4>::new();
+        for word in ["a", "b", "a", "c", "a"].iter() {
+            *map.entry(*word).or_default() += 1;
+        }
+        assert_eq!(map.get("a"), Some(&3));
+        match map.entry("d") {
+            HashMapEntry::Vacant(entry) => assert_eq!(entry.try_insert(1), Err(NotEnoughSpaceError)),
+            HashMapEntry::Occupied(_) => unreachable!(),
+        }
+        match map.entry("b") {
+            HashMapEntry::Occupied(entry) => assert_eq!(entry.remove(), 1),
+            HashMapEntry::Vacant(_) => unreachable!(),
+        }
+        assert_eq!(map.len(), 2);
+    }
+}
+
+mod set {
+    use super::*;
+
+    #[test]
+    fn insert_remove() {
+        let mut set = StackSet::<_, 3>::new();
+        assert!(set.insert(2));
+        assert!(set.insert(0));
+        assert_eq!(set.try_insert(2), Ok(false));
+        assert_eq!(set.try_insert(1), Ok(true));
+        assert_eq!(set.try_insert(3), Err(NotEnoughSpaceError));
+        assert_eq!(set.as_slice(), &[0, 1, 2]);
+        assert!(set.remove(&1));
+        assert!(!set.remove(&1));
+        assert_eq!(set.len(), 2);
+
+        let a: StackSet<_, 4> = vec![1, 2, 2, 3].into_iter().collect();
+        let b: StackSet<_, 4> = vec![3, 4, 3].into_iter().collect();
+        assert_eq!(a.union(&b).unwrap().as_slice(), &[1, 2, 3, 4]);
+        assert_eq!(a.intersection(&b).as_slice(), &[3]);
+        assert_eq!(a.difference(&b).as_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn bit_set() {
+        let mut set = StackBitSetWords::<2>::new();
+        assert!(set.insert(0));
+        assert!(set.insert(63));
+        assert!(set.insert(64));
+        assert!(!set.insert(64));
+        assert!(set.insert(127));
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(63));
+        assert!(!set.contains(128));
+        assert!(set.remove(63));
+        assert!(!set.remove(63));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 64, 127]);
+        assert_eq!(set.iter().len(), 3);
+
+        let other: StackBitSetWords<2> = vec![1, 64].into_iter().collect();
+        assert_eq!(set.union(&other).iter().collect::<Vec<_>>(), vec![0, 1, 64, 127]);
+        assert_eq!(set.intersection(&other).iter().collect::<Vec<_>>(), vec![64]);
+        assert_eq!(set.difference(&other).iter().collect::<Vec<_>>(), vec![0, 127]);
+        assert!(set.intersection(&other).is_subset(&other));
+    }
+
+    #[test]
+    #[should_panic]
+    fn bit_set_insert_fail() {
+        let mut set = StackBitSetWords::<1>::new();
+        set.insert(64);
+    }
+}
+
+mod slab {
+    use super::*;
+
+    #[test]
+    fn insert_remove() {
+        let mut slab = StackSlab::<_, 3>::new();
+        let a = slab.insert(0).unwrap();
+        let b = slab.insert(1).unwrap();
+        let c = slab.insert(2).unwrap();
+        assert_eq!(slab.insert(3), Err(3));
+        assert_eq!(slab.remove(b), Some(1));
+        assert_eq!(slab.remove(b), None);
+        assert_eq!(slab.get(c), Some(&2));
+
+        let d = slab.insert(4).unwrap();
+        assert_eq!(d.index(), b.index());
+        assert_eq!(slab.get(b), None);
+        *slab.get_mut(d).unwrap() += 1;
+        assert_eq!(slab.iter().map(|(_, v)| *v).collect::<Vec<_>>(), vec![0, 5, 2]);
+
+        slab.clear();
+        assert!(slab.is_empty());
+        assert!(!slab.contains(a));
+        let e = slab.insert(6).unwrap();
+        assert_eq!(e.index(), 0);
+        assert_eq!(slab.get(a), None);
+        assert_eq!(slab.get(e), Some(&6));
+    }
+
+    #[test]
+    fn clear_after_remove() {
+        let mut slab = StackSlab::<_, 2>::new();
+        let a = slab.insert("a".to_string()).unwrap();
+        slab.insert("b".to_string()).unwrap();
+        slab.remove(a);
+        slab.clear();
+
+        let x = slab.insert("x".to_string()).unwrap();
+        slab.insert("y".to_string()).unwrap();
+        assert_eq!(slab.insert("z".to_string()), Err("z".to_string()));
+        assert_eq!(slab.len(), 2);
+        assert_eq!(slab.get(x).map(String::as_str), Some("x"));
+    }
+}
+
+mod non_empty {
+    use super::*;
+
+    use std::convert::TryFrom;
+
+    #[test]
+    fn try_from() {
+        assert_eq!(NonEmptyStackVec::try_from(StackVec::<i32, 3>::new()), Err(EmptyError));
+        let vec = NonEmptyStackVec::try_from(stack_vec![2, 5, 1, 5; cap = 4]).unwrap();
+        assert_eq!(vec.first(), &2);
+        assert_eq!(vec.last(), &5);
+        assert_eq!(vec.min(), &1);
+        assert_eq!(vec.max(), &5);
+    }
+
+    #[test]
+    fn keeps_first() {
+        let mut vec = NonEmptyStackVec::<_, 3>::new(0);
+        vec.push(1);
+        vec.push(2);
+        vec.truncate(0);
+        assert_eq!(vec.as_slice(), &[0]);
+        assert_eq!(vec.pop(), None);
+        assert_eq!(vec.try_remove(0), None);
+        vec.insert(0, 5);
+        assert_eq!(vec.try_remove(0), Some(5));
+        assert_eq!(vec.len(), 1);
+    }
+}
+
+#[test]
+fn checkpoint() {
+    /// Collects all paths from `0` to `target` in a DAG, built on a single stack of nodes.
+    fn paths(
+        edges: &[(usize, usize)],
+        target: usize,
+        path: &mut StackVec<usize, 8>,
+        found: &mut Vec<Vec<usize>>,
+    ) {
+        let node = *path.last().unwrap();
+        if node == target {
+            found.push(path.to_vec());
+            return;
+        }
+        for &(_, next) in edges.iter().filter(|(from, _)| *from == node) {
+            let mut cp = path.checkpoint();
+            cp.push(next);
+            paths(edges, target, &mut cp, found);
+        }
+    }
+
+    let mut path = stack_vec![0; cap = 8];
+    let mut found = Vec::new();
+    paths(&[(0, 1), (0, 2), (1, 3), (2, 3), (1, 2)], 3, &mut path, &mut found);
+    assert_eq!(path, stack_vec![0]);
+    assert_eq!(found, vec![vec![0, 1, 3], vec![0, 1, 2, 3], vec![0, 2, 3]]);
+
+    let mut cp = path.checkpoint();
+    cp.push(1);
+    cp.commit();
+    assert_eq!(path, stack_vec![0, 1]);
+    path.checkpoint().clear();
+    assert_eq!(path, stack_vec![]);
+}
+
+mod policy {
+    use super::*;
+
+    #[test]
+    fn drop_new() {
+        let mut vec = PolicyStackVec::<_, 3, DropNew>::new();
+        vec.extend(0..2);
+        vec.insert(0, 9);
+        vec.push(5);
+        vec.insert(1, 7);
+        assert_eq!(vec.as_slice(), &[9, 0, 1]);
+
+        let mut iter = 0..10;
+        vec.clear();
+        vec.extend(&mut iter);
+        assert_eq!(iter.next(), Some(3));
+    }
+
+    #[test]
+    fn drop_oldest() {
+        let mut vec = PolicyStackVec::<_, 3, DropOldest>::new();
+        vec.extend(0..4);
+        assert_eq!(vec.as_slice(), &[1, 2, 3]);
+        vec.insert(2, 9);
+        assert_eq!(vec.as_slice(), &[2, 9, 3]);
+        vec.insert(0, 8);
+        assert_eq!(vec.as_slice(), &[2, 9, 3]);
+
+        let mut vec = PolicyStackVec::<_, 0, DropOldest>::new();
+        vec.push(1);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn checked() {
+        let mut vec = PolicyStackVec::<_, 2, Checked>::new();
+        assert_eq!(vec.push(0), Ok(()));
+        assert_eq!(vec.insert(0, 1), Ok(()));
+        assert_eq!(vec.insert(0, 2), Err(NotEnoughSpaceError));
+        assert_eq!(vec.as_slice(), &[1, 0]);
+        assert!(PolicyStackVec::<_, 2, Checked>::try_from_iter(0..3).is_err());
+        assert_eq!(PolicyStackVec::<_, 2, Checked>::try_from_iter(0..2).unwrap().as_slice(), &[0, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic() {
+        let _: PolicyStackVec<_, 2> = (0..3).collect();
+    }
+}
+
+#[test]
+fn try_from_iter() {
+    let vec = StackVec::<_, 4>::try_from_iter(vec![1, 2, 3]).unwrap();
+    assert_eq!(vec, stack_vec![1, 2, 3]);
+    let vec = StackVec::<_, 3>::try_from_iter(vec![1, 2, 3]).unwrap();
+    assert_eq!(vec, stack_vec![1, 2, 3]);
+
+    let (vec, rest) = StackVec::<_, 2>::try_from_iter(vec![1, 2, 3, 4]).unwrap_err();
+    assert_eq!(vec, stack_vec![1, 2]);
+    assert_eq!(rest.collect::<Vec<_>>(), vec![3, 4]);
+
+    // the size hint of `filter` is not exact
+    let vec = StackVec::<_, 2>::try_from_iter((0..4).filter(|x| x % 2 == 0)).unwrap();
+    assert_eq!(vec, stack_vec![0, 2]);
+    let (vec, rest) = StackVec::<_, 2>::try_from_iter((0..6).filter(|x| x % 2 == 0)).unwrap_err();
+    assert_eq!(vec, stack_vec![0, 2]);
+    assert_eq!(rest.collect::<Vec<_>>(), vec![4]);
+}
+
+#[test]
+fn iterator_ext() {
+    let mut chunks = (0..5).stack_chunks::<2>();
+    assert_eq!(chunks.size_hint(), (3, Some(3)));
+    assert_eq!(chunks.next(), Some(stack_vec![0, 1]));
+    assert_eq!(chunks.next(), Some(stack_vec![2, 3]));
+    assert_eq!(chunks.next(), Some(stack_vec![4; cap = 2]));
+    assert_eq!(chunks.next(), None);
+    assert_eq!((0..0).stack_chunks::<2>().next(), None);
+
+    assert_eq!((0..3).try_collect_stack_vec::<3>(), Ok(stack_vec![0, 1, 2]));
+    assert_eq!((0..4).try_collect_stack_vec::<3>(), Err(NotEnoughSpaceError));
+    let evens = (0..8).filter(|x| x % 2 == 0).try_collect_stack_vec::<4>();
+    assert_eq!(evens, Ok(stack_vec![0, 2, 4, 6]));
+    assert!((0..9).filter(|x| x % 2 == 0).try_collect_stack_vec::<4>().is_err());
+    let mut iter = 0..6;
+    assert_eq!(iter.by_ref().try_collect_stack_vec::<3>(), Err(NotEnoughSpaceError));
+    assert_eq!(iter.next(), Some(4));
+}
+
+#[test]
+fn partition_and_group_by() {
+    let (small, big) = StackVec::from([5, 1, 7, 2]).partition(|x| *x < 4);
+    assert_eq!(small.as_slice(), &[1, 2]);
+    assert_eq!(big.as_slice(), &[5, 7]);
+
+    let vec = stack_vec![1, 2, 4, 5, 7; cap = 5];
+    let runs = vec.group_by::<3, _>(|a, b| a + 1 == *b);
+    assert_eq!(runs.len(), 3);
+    assert_eq!(runs[1], &[4, 5]);
+    assert_eq!(vec.try_group_by::<2, _>(|a, b| a + 1 == *b), Err(NotEnoughSpaceError));
+    assert!(StackVec::<i32, 0>::new().group_by::<0, _>(|_, _| true).is_empty());
+}
+
+#[rustversion::since(1.77)]
+#[test]
+fn slice_chunk_by() {
+    // not shadowed by `StackVec::group_by`
+    let vec = StackVec::from([1, 1, 2]);
+    assert_eq!(vec.chunk_by(|a, b| a == b).count(), 2);
+}
+
+#[test]
+fn cursor_mut() {
+    let mut vec = StackVec::<_, 5>::from_array([1, 2, 3]).unwrap();
+    let mut cursor = vec.cursor_mut();
+    cursor.move_prev();
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_prev(), Some(&mut 3));
+    cursor.insert_after(0).unwrap();
+    cursor.insert_before(4).unwrap();
+    assert_eq!(cursor.insert_before(5), Err(NotEnoughSpaceError));
+    assert_eq!(cursor.index(), None);
+
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(1));
+    assert_eq!(cursor.current(), Some(&mut 2));
+    let tail = cursor.split_at_cursor();
+    assert_eq!(cursor.index(), None);
+    assert_eq!(tail.as_slice(), &[2, 3, 4]);
+    assert_eq!(vec.as_slice(), &[0]);
+}
+
+#[test]
+fn into_iter() {
+    let mut iter = StackVec::from([0, 1, 2, 3, 4, 5]).into_iter();
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next_back(), Some(5));
+    assert_eq!(iter.as_slice(), &[1, 2, 3, 4]);
+    iter.as_mut_slice()[0] = 10;
+
+    let cloned = iter.clone();
+    assert_eq!(iter.advance_by(3), Ok(()));
+    assert_eq!(iter.advance_by(3), Err(std::num::NonZeroUsize::new(2).unwrap()));
+    assert_eq!(iter.next(), None);
+    assert_eq!(cloned.collect::<Vec<_>>(), vec![10, 2, 3, 4]);
+
+    let mut iter = StackVec::from([0, 1, 2, 3]).into_iter();
+    assert_eq!(iter.nth_back(1), Some(2));
+    assert_eq!(iter.nth(5), None);
+    assert_eq!(iter.len(), 0);
+    assert_eq!(IntoIter::<i32, 3>::default().into_stack_vec(), StackVec::new());
+
+    use std::mem::size_of;
+    assert_eq!(
+        size_of::<IntoIter<[u8; 64], 1024>>(),
+        size_of::<StackVec<[u8; 64], 1024>>() + size_of::<usize>(),
+    );
+}
+
+#[test]
+fn from_array_moves_elements() {
+    let vec = StackVec::<_, 3>::from_array([String::from("a"), String::from("b")]).unwrap();
+    assert_eq!(vec.as_slice(), &["a", "b"]);
+    assert_eq!(stack_vec![String::from("c"); cap = 2][0], "c");
+}
+
+#[test]
+fn new_boxed() {
+    // 8 MB, more than the stack of a test thread
+    let mut vec = StackVec::<u64, 1_000_000>::new_boxed();
+    vec.extend(0..10);
+    vec.push(10);
+    assert_eq!(vec.len(), 11);
+    assert_eq!(vec.pop(), Some(10));
+}
+
+mod slice_vec {
+    use super::*;
+
+    use std::mem::MaybeUninit;
+
+    #[test]
+    fn operations() {
+        let mut buf: [MaybeUninit<String>; 4] = unsafe { MaybeUninit::uninit().assume_init() };
+        let mut vec = SliceVec::new(&mut buf);
+        assert_eq!(vec.capacity(), 4);
+        vec.extend(["b", "d"].iter().map(|s| s.to_string()));
+        vec.insert(0, "a".to_string());
+        assert_eq!(vec.try_insert(5, "x".to_string()), Err(InsertError::IndexOutOfRange));
+        vec.insert(2, "c".to_string());
+        assert!(vec.is_full());
+        assert_eq!(vec.try_push("e".to_string()), Err(NotEnoughSpaceError));
+        assert_eq!(vec.remove(1), "b");
+        assert_eq!(vec.try_remove(3), None);
+        assert_eq!(vec.pop(), Some("d".to_string()));
+        vec.truncate(1);
+        assert_eq!(vec.as_slice(), &["a"]);
+    }
+
+    #[test]
+    fn drain() {
+        let mut vec = StackVec::<_, 6>::from_array([0, 1, 2, 3, 4, 5]).unwrap();
+        {
+            let mut slice_vec = vec.as_slice_vec();
+            let mut drain = slice_vec.drain(1..=4);
+            assert_eq!(drain.next(), Some(1));
+            assert_eq!(drain.next_back(), Some(4));
+            assert_eq!(drain.as_slice(), &[2, 3]);
+        }
+        assert_eq!(vec.as_slice(), &[0, 5]);
+
+        let mut slice_vec = vec.as_slice_vec();
+        assert_eq!(slice_vec.drain(..).rev().collect::<Vec<_>>(), vec![5, 0]);
+        slice_vec.push(7);
+        std::mem::forget(slice_vec);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "usize::MAX")]
+    fn drain_overflow() {
+        let mut vec = StackVec::<_, 2>::from([0, 1]);
+        vec.as_slice_vec().drain(..=usize::MAX);
+    }
+}
+
+#[test]
+fn view() {
+    fn edit(view: &mut StackVecView<String>) {
+        view.extend(["b", "d"].iter().map(|s| s.to_string()));
+        view.insert(0, "a".to_string());
+        assert_eq!(view.try_insert(2, "c".to_string()), Err(InsertError::NotEnoughSpace));
+        assert_eq!(view.remove(1), "b");
+        assert_eq!(view.try_remove(2), None);
+        view.push("e".to_string());
+    }
+
+    let mut vec = StackVec::<_, 3>::new();
+    edit(vec.as_view_mut());
+    assert_eq!(vec.as_slice(), &["a", "d", "e"]);
+    assert_eq!(vec.as_view().capacity(), 3);
+    assert_eq!(vec.as_view_mut().pop(), Some("e".to_string()));
+    vec.as_view_mut().truncate(1);
+    assert_eq!(vec.len(), 1);
+
+    let mut zst = StackVec::<(), 2>::new();
+    zst.as_view_mut().push(());
+    assert_eq!(zst.len(), 1);
+}
+
+fn rotate_in<V: CapacityVec<i32> + ?Sized>(vec: &mut V) -> Result<(), InsertError> {
+    let first = vec.remove(0);
+    vec.try_insert(vec.len(), first)?;
+    vec.try_insert(0, -1)
+}
+
+#[test]
+fn capacity_vec() {
+    let mut stack = StackVec::<_, 3>::from_array([1, 2]).unwrap();
+    assert_eq!(rotate_in(&mut stack), Ok(()));
+    assert_eq!(stack.as_slice(), &[-1, 2, 1]);
+    assert_eq!(rotate_in(stack.as_view_mut()), Err(InsertError::NotEnoughSpace));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn capacity_vec_for_vec() {
+    let mut heap = Vec::with_capacity(2);
+    heap.extend([1, 2].iter().copied());
+    assert_eq!(rotate_in(&mut heap), Err(InsertError::NotEnoughSpace));
+    assert_eq!(heap, vec![2, 1]);
+    assert_eq!(CapacityVec::try_remove(&mut heap, 2), None);
+    assert_eq!(CapacityVec::try_push(&mut heap, 3), Err(NotEnoughSpaceError));
+    CapacityVec::clear(&mut heap);
+    assert!(CapacityVec::is_empty(&heap));
 }
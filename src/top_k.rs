@@ -0,0 +1,171 @@
+use super::StackVec;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::FromIterator;
+use std::slice;
+
+/// A collector keeping the `N` greatest elements pushed into it, sorted from the best one.
+///
+/// Once full, pushing an element evicts the worst one, so it never runs out of space.
+/// Use [`std::cmp::Reverse`] to keep the `N` least elements instead.
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// let top: TopK<_, 3> = vec![4, 9, 1, 7, 3, 8].into_iter().collect();
+///
+/// assert_eq!(top.as_slice(), &[9, 8, 7]);
+/// assert_eq!(top.into_sorted(), stack_vec![9, 8, 7]);
+/// ```
+pub struct TopK<T, const N: usize> {
+    data: StackVec<T, N>,
+}
+
+impl<T, const N: usize> TopK<T, N> {
+    /// Maximal number of kept elements.
+    pub const CAPACITY: usize = N;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            data: StackVec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.data.len() == Self::CAPACITY
+    }
+
+    /// Returns the kept elements, from the best to the worst one.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Iterates from the best to the worst element.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    #[inline]
+    pub fn best(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns the element which is evicted first.
+    #[inline]
+    pub fn worst(&self) -> Option<&T> {
+        self.data.last()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the kept elements, from the best to the worst one.
+    #[inline]
+    pub fn into_sorted(self) -> StackVec<T, N> {
+        self.data
+    }
+
+    /// Pushes a value comparing it by the key extracted with `f`.
+    /// Returns the element which didn't make it, either the evicted one or `value` itself.
+    ///
+    /// The same key has to be used for every push, otherwise the order is unspecified.
+    pub fn push_by_key<K, F>(&mut self, value: T, mut f: F) -> Option<T>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.push_by(value, |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Pushes a value comparing it with `compare`.
+    /// Returns the element which didn't make it, either the evicted one or `value` itself.
+    ///
+    /// The same comparator has to be used for every push, otherwise the order is unspecified.
+    pub fn push_by<F>(&mut self, value: T, mut compare: F) -> Option<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // on ties the older element is kept, so `value` goes after its equals
+        let idx = self.data
+            .binary_search_by(|probe| match compare(probe, &value) {
+                Ordering::Less => Ordering::Greater,
+                _ => Ordering::Less,
+            })
+            .unwrap_err();
+        if idx == Self::CAPACITY {
+            return Some(value);
+        }
+
+        let evicted = if self.is_full() {
+            self.data.pop()
+        } else {
+            None
+        };
+        unsafe { self.data.insert_unchecked(idx, value); }
+        evicted
+    }
+}
+
+impl<T: Ord, const N: usize> TopK<T, N> {
+    /// Pushes a value.
+    /// Returns the element which didn't make it, either the evicted one or `value` itself.
+    #[inline]
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.push_by(value, T::cmp)
+    }
+}
+
+impl<T, const N: usize> Default for TopK<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for TopK<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+impl<T: Ord, const N: usize> Extend<T> for TopK<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> FromIterator<T> for TopK<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut top = TopK::new();
+        top.extend(iter);
+        top
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a TopK<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,212 @@
+use super::raw::RawVec;
+use super::{InsertError, NotEnoughSpaceError, StackVec};
+
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ops;
+use std::ptr;
+use std::slice;
+
+/// A [`StackVec`] with its capacity erased, so that non-generic functions can handle a
+/// [`StackVec`] of any capacity.
+///
+/// It is unsized and only exists behind a reference, see [`StackVec::as_view`] and
+/// [`StackVec::as_view_mut`].
+///
+/// # Example
+/// ```
+/// # use stack_vec::*;
+/// fn fill(vec: &mut StackVecView<u8>) {
+///     while vec.try_push(vec.len() as u8).is_ok() {}
+/// }
+///
+/// let mut small = StackVec::<u8, 2>::new();
+/// let mut big = StackVec::<u8, 4>::new();
+/// fill(small.as_view_mut());
+/// fill(big.as_view_mut());
+/// assert_eq!(small.as_slice(), &[0, 1]);
+/// assert_eq!(big.as_slice(), &[0, 1, 2, 3]);
+/// ```
+#[repr(C)] // same layout as `StackVec`
+pub struct StackVecView<T> {
+    len: usize,
+    data: [MaybeUninit<T>],
+}
+
+impl<T, const N: usize> StackVec<T, N> {
+    /// Borrows the [`StackVec`] as a [`StackVecView`], which is the same for every capacity.
+    #[inline]
+    pub fn as_view(&self) -> &StackVecView<T> {
+        // the slice metadata becomes the length of `StackVecView::data`
+        let ptr = ptr::slice_from_raw_parts(self as *const Self as *const MaybeUninit<T>, N);
+        unsafe { &*(ptr as *const StackVecView<T>) }
+    }
+
+    /// Borrows the [`StackVec`] as a [`StackVecView`], which is the same for every capacity.
+    /// Changes made through the view, e.g. pushed elements, are kept by the [`StackVec`].
+    #[inline]
+    pub fn as_view_mut(&mut self) -> &mut StackVecView<T> {
+        let ptr = ptr::slice_from_raw_parts_mut(self as *mut Self as *mut MaybeUninit<T>, N);
+        unsafe { &mut *(ptr as *mut StackVecView<T>) }
+    }
+}
+
+impl<T> StackVecView<T> {
+    /// Length of the underlying array.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.data.as_ptr() as _
+    }
+
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.data.as_mut_ptr() as _
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len) }
+    }
+
+    /// Pushes a value after the last element, panics if there is not space available.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.raw().push(value, "StackVec");
+    }
+
+    /// Pushes a value after the last element returning a `Result`.
+    #[inline]
+    pub fn try_push(&mut self, value: T) -> Result<(), NotEnoughSpaceError> {
+        self.raw().try_push(value)
+    }
+
+    #[inline]
+    pub(crate) unsafe fn push_unchecked(&mut self, value: T) {
+        unsafe { self.raw().push_unchecked(value); }
+    }
+
+    /// Inserts a value at specified index by pushing elements from `idx` by one.
+    /// Panics on invalid index or if there is not space available.
+    #[inline]
+    pub fn insert(&mut self, idx: usize, value: T) {
+        self.raw().insert(idx, value, "StackVec");
+    }
+
+    /// Inserts a value at specified index by pushing elements from `idx` by one.
+    #[inline]
+    pub fn try_insert(&mut self, idx: usize, value: T) -> Result<(), InsertError> {
+        self.raw().try_insert(idx, value)
+    }
+
+    #[inline]
+    pub(crate) unsafe fn insert_unchecked(&mut self, idx: usize, value: T) {
+        unsafe { self.raw().insert_unchecked(idx, value); }
+    }
+
+    /// Pops the last element.
+    /// If exists returns it in `Some`, otherwise `None`.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.raw().pop()
+    }
+
+    /// Removes an element specified by `idx`.
+    /// Panics if `idx >= self.len()`.
+    #[inline]
+    pub fn remove(&mut self, idx: usize) -> T {
+        self.raw().remove(idx)
+    }
+
+    /// Removes an element specified by `idx`.
+    /// If exists returns it in `Some`, otherwise `None`.
+    #[inline]
+    pub fn try_remove(&mut self, idx: usize) -> Option<T> {
+        self.raw().try_remove(idx)
+    }
+
+    #[inline]
+    pub(crate) unsafe fn remove_unchecked(&mut self, idx: usize) -> T {
+        unsafe { self.raw().remove_unchecked(idx) }
+    }
+
+    /// Truncates to specified length.
+    /// Does nothing if `new_len` is greater than current length.
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        self.raw().truncate(new_len);
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    #[inline]
+    fn raw(&mut self) -> RawVec<'_, T> {
+        RawVec {
+            data: &mut self.data,
+            len: &mut self.len,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for StackVecView<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for StackVecView<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T> ops::Deref for StackVecView<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T> ops::DerefMut for StackVecView<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> Extend<T> for StackVecView<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.raw().extend(iter, "StackVec");
+    }
+}